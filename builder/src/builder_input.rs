@@ -1,27 +1,60 @@
-use syn::{braced, spanned::Spanned, Attribute, Field, Token};
+use syn::{braced, ext::IdentExt, spanned::Spanned, Attribute, Field, Token};
 
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 #[allow(dead_code)]
 pub struct BuilderInput {
+    attr: BuilderStructAttribute,
     pub vis: syn::Visibility,
     pub struct_token: Token![struct],
     pub ident: syn::Ident,
+    pub generics: syn::Generics,
     pub brace_token: syn::token::Brace,
     fields: syn::punctuated::Punctuated<BuilderInputField, Token![,]>,
+    // Indices into `fields` of the computed fields, in the order they have to be evaluated
+    computed_order: Vec<usize>,
 }
 
 impl syn::parse::Parse for BuilderInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut attr: BuilderStructAttribute = input.parse()?;
         let vis: syn::Visibility = input.parse()?;
         let lookahead = input.lookahead1();
         if lookahead.peek(Token![struct]) {
             let content;
+            let struct_token = input.parse()?;
+            let ident = input.parse()?;
+            let mut generics: syn::Generics = input.parse()?;
+            generics.where_clause = input.parse()?;
+            let brace_token = braced!(content in input);
+            let mut fields = content.parse_terminated(BuilderInputField::parse, Token![,])?;
+
+            // Every attribute is parsed before anything is reported, so a single compile
+            // shows all of the mistakes instead of only the first one
+            let mut errors = std::mem::take(&mut attr.errors);
+            for f in fields.iter_mut() {
+                errors.append(&mut f.errors);
+            }
+            if let Some(const_fn) = &attr.const_fn {
+                errors.extend(const_builder_errors(const_fn, &fields));
+            }
+            errors.extend(method_name_conflicts(&fields));
+            let computed_order = computed_order(&fields).unwrap_or_else(|err| {
+                errors.push(err);
+                vec![]
+            });
+            if let Some(error) = combine_errors(errors) {
+                return Err(error);
+            }
+
             Ok(BuilderInput {
+                attr,
                 vis,
-                struct_token: input.parse()?,
-                ident: input.parse()?,
-                brace_token: braced!(content in input),
-                fields: content.parse_terminated(BuilderInputField::parse, Token![,])?,
+                struct_token,
+                ident,
+                generics,
+                brace_token,
+                fields,
+                computed_order,
             })
         } else {
             Err(lookahead.error())
@@ -29,68 +62,402 @@ impl syn::parse::Parse for BuilderInput {
     }
 }
 
+// Attributes placed on the struct itself, ie. `#[builder(debug)]`
+struct BuilderStructAttribute {
+    debug: bool,
+    build_fn: BuildFnAttribute,
+    // `#[builder(const)]`, usable in `const` and `static` items
+    const_fn: Option<Token![const]>,
+    // `#[builder(vis = "...")]`, defaults to the visibility of the struct
+    vis: Option<syn::Visibility>,
+    // `#[builder(serde)]`, only accepted with the `serde` feature of this crate
+    serde: bool,
+    errors: Vec<syn::Error>,
+}
+
+const STRUCT_KEYS: &[&str] = &["debug", "build_fn", "const", "vis", "serde"];
+const BUILD_FN_KEYS: &[&str] = &["partial", "or_default", "wrappers"];
+const WRAPPER_KEYS: &[&str] = &["arc", "rc", "box"];
+const FIELD_KEYS: &[&str] = &["each", "redact", "computed", "setter"];
+const SETTER_KEYS: &[&str] = &["transform", "try_transform"];
+
+// Extra build methods requested through `#[builder(build_fn(...))]`
+#[derive(Default)]
+struct BuildFnAttribute {
+    partial: bool,
+    or_default: bool,
+    // Smart pointers listed in `build_fn(wrappers(...))`
+    wrappers: Vec<BuildWrapper>,
+}
+
+#[derive(PartialEq)]
+enum BuildWrapper {
+    Box,
+    Rc,
+    Arc,
+}
+
+impl syn::parse::Parse for BuilderStructAttribute {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs: Vec<syn::Attribute> = input.call(Attribute::parse_outer)?;
+        let mut debug = false;
+        let mut build_fn = BuildFnAttribute::default();
+        let mut const_fn = None;
+        let mut vis = None;
+        let mut serde = false;
+        let mut errors = vec![];
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+            let result = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("debug") {
+                    debug = true;
+                    Ok(())
+                } else if meta.path.is_ident("const") {
+                    const_fn = Some(Token![const](meta.path.span()));
+                    Ok(())
+                } else if meta.path.is_ident("vis") {
+                    let lit_str: syn::LitStr = meta.value()?.parse()?;
+                    vis = Some(lit_str.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("serde") {
+                    // The feature alone isn't enough, it applies to every crate in the build
+                    // and most of them don't depend on serde
+                    if !cfg!(feature = "serde") {
+                        errors.push(meta.error(
+                            "`serde` needs the `serde` feature of derive_builder to be enabled",
+                        ));
+                    }
+                    serde = true;
+                    Ok(())
+                } else if meta.path.is_ident("build_fn") {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("partial") {
+                            build_fn.partial = true;
+                            Ok(())
+                        } else if meta.path.is_ident("or_default") {
+                            build_fn.or_default = true;
+                            Ok(())
+                        } else if meta.path.is_ident("wrappers") {
+                            meta.parse_nested_meta(|meta| {
+                                let wrapper = if meta.path.is_ident("box") {
+                                    BuildWrapper::Box
+                                } else if meta.path.is_ident("rc") {
+                                    BuildWrapper::Rc
+                                } else if meta.path.is_ident("arc") {
+                                    BuildWrapper::Arc
+                                } else {
+                                    errors.push(unknown_key_error(&meta, "wrappers", WRAPPER_KEYS));
+                                    return skip_meta_value(&meta);
+                                };
+                                if !build_fn.wrappers.contains(&wrapper) {
+                                    build_fn.wrappers.push(wrapper);
+                                }
+                                Ok(())
+                            })
+                        } else {
+                            errors.push(unknown_key_error(&meta, "build_fn", BUILD_FN_KEYS));
+                            skip_meta_value(&meta)
+                        }
+                    })
+                } else {
+                    errors.push(unknown_key_error(&meta, "builder", STRUCT_KEYS));
+                    skip_meta_value(&meta)
+                }
+            });
+            if let Err(err) = result {
+                errors.push(err);
+            }
+        }
+        Ok(BuilderStructAttribute {
+            debug,
+            build_fn,
+            const_fn,
+            vis,
+            serde,
+            errors,
+        })
+    }
+}
+
 struct BuilderInputField {
     field: syn::Field,
     optional: Option<syn::Type>,
     each: Option<BuilderEachAttribute>,
+    redact: bool,
+    // Not settable, evaluated in `build()` from the other fields
+    computed: Option<syn::Expr>,
+    setter: Option<SetterTransform>,
+    errors: Vec<syn::Error>,
+}
+
+// `#[builder(setter(transform = |...| ...))]`, the setter takes the closure's arguments and
+// stores what it returns. With `try_transform` the closure returns a Result and the error is
+// reported by `build()`.
+struct SetterTransform {
+    closure: syn::ExprClosure,
+    fallible: bool,
 }
 
 struct BuilderEachAttribute {
-    val: String,
+    val: syn::Ident,
     ty: syn::Type,
 }
 
 impl syn::parse::Parse for BuilderInputField {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let attrs: Vec<syn::Attribute> = input.call(Attribute::parse_outer)?;
-        let builder_attrs: Vec<&Attribute> = attrs
-            .iter()
-            .filter(|attr| attr.path().is_ident("builder"))
-            .collect();
-        // TODO: Get Vec type if each is set as it's needed for the set method
-        let each_attr: Option<syn::LitStr> = if let Some(attr) = builder_attrs.first() {
-            let mut each: Option<syn::LitStr> = None;
-            attr.parse_nested_meta(|meta| {
+        let mut redact = false;
+        let mut computed: Option<syn::Expr> = None;
+        let mut each_attr: Option<syn::LitStr> = None;
+        let mut setter: Option<SetterTransform> = None;
+        let mut errors = vec![];
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+            let result = attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("each") {
-                    each = Some(meta.value()?.parse()?);
+                    each_attr = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("redact") {
+                    redact = true;
+                    Ok(())
+                } else if meta.path.is_ident("computed") {
+                    let lit_str: syn::LitStr = meta.value()?.parse()?;
+                    match lit_str.parse() {
+                        Ok(expr) => computed = Some(expr),
+                        Err(err) => errors.push(err),
+                    }
                     Ok(())
+                } else if meta.path.is_ident("setter") {
+                    meta.parse_nested_meta(|meta| {
+                        let fallible = if meta.path.is_ident("transform") {
+                            false
+                        } else if meta.path.is_ident("try_transform") {
+                            true
+                        } else {
+                            errors.push(unknown_key_error(&meta, "setter", SETTER_KEYS));
+                            return skip_meta_value(&meta);
+                        };
+                        let closure: syn::ExprClosure = meta.value()?.parse()?;
+                        if setter.is_some() {
+                            errors.push(meta.error("only one setter transform can be given"));
+                        }
+                        if let Some(input) = closure
+                            .inputs
+                            .iter()
+                            .find(|input| !matches!(input, syn::Pat::Type(_)))
+                        {
+                            errors.push(syn::Error::new_spanned(
+                                input,
+                                "setter transform arguments need a type annotation",
+                            ));
+                        }
+                        setter = Some(SetterTransform { closure, fallible });
+                        Ok(())
+                    })
                 } else {
-                    Err(syn::Error::new(
-                        attr.meta.span(),
-                        "expected `builder(each = \"...\")`",
-                    ))
+                    errors.push(unknown_key_error(&meta, "builder", FIELD_KEYS));
+                    skip_meta_value(&meta)
                 }
-            })?;
-            each
-        } else {
-            None
-        };
+            });
+            if let Err(err) = result {
+                errors.push(err);
+            }
+        }
 
         let field = Field::parse_named(input)?;
-        let optional = type_confirm(vec!["Option:", "std:core:Option"].into_iter(), &field);
+        let optional = type_confirm(
+            vec!["Option:", "std:option:Option:", "core:option:Option:"].into_iter(),
+            &field,
+        );
 
         let mut each: Option<BuilderEachAttribute> = None;
 
-        if let Some(each_str) = each_attr {
-            let Some(each_ty) = type_confirm(vec!["Vec:", "std:vec:Vec"].into_iter(), &field)
-            else {
-                return Err(syn::Error::new(field.span(), "Type should be a Vec"));
-            };
+        if computed.is_some() && setter.is_some() {
+            errors.push(syn::Error::new(
+                field.span(),
+                "computed fields can't have a setter",
+            ));
+        }
 
-            each = Some(BuilderEachAttribute {
-                val: each_str.value(),
-                ty: each_ty,
-            });
+        if let Some(each_str) = each_attr {
+            if computed.is_some() {
+                errors.push(syn::Error::new(
+                    field.span(),
+                    "computed fields can't have an `each` setter",
+                ));
+            }
+            let each_ty = type_confirm(
+                vec!["Vec:", "std:vec:Vec:", "alloc:vec:Vec:"].into_iter(),
+                &field,
+            );
+            match (each_str.parse_with(syn::Ident::parse_any), each_ty) {
+                (Ok(val), Some(ty)) => {
+                    // Keywords like `type` can only be used as a method name in raw form
+                    let val = if syn::parse_str::<syn::Ident>(&val.to_string()).is_ok() {
+                        val
+                    } else {
+                        syn::Ident::new_raw(&val.to_string(), val.span())
+                    };
+                    each = Some(BuilderEachAttribute { val, ty });
+                }
+                (Err(err), _) => errors.push(err),
+                (_, None) => errors.push(syn::Error::new(field.span(), "Type should be a Vec")),
+            }
         }
         Ok(Self {
             field,
             optional,
             each,
+            redact,
+            computed,
+            setter,
+            errors,
         })
     }
 }
 
+impl BuilderInputField {
+    // The type the field is staged as inside the builder. Optional and repeated fields are
+    // stored as is, required fields are wrapped in an Option until they're set.
+    fn builder_ty(&self) -> proc_macro2::TokenStream {
+        let ty = &self.field.ty;
+        if self.optional.is_some() || self.each.is_some() {
+            quote! { #ty }
+        } else {
+            quote! { std::option::Option<#ty> }
+        }
+    }
+
+    // The initial value of the field in a fresh builder
+    fn builder_init(&self) -> proc_macro2::TokenStream {
+        let name = &self.field.ident;
+        if self.each.is_some() {
+            quote_spanned! { self.field.span() =>
+                #name: std::vec::Vec::new()
+            }
+        } else {
+            quote! {
+                #name: std::option::Option::None
+            }
+        }
+    }
+}
+
 impl BuilderInput {
+    // The fields that are staged in the builder, ie. every field that isn't computed
+    fn builder_fields(&self) -> impl Iterator<Item = &BuilderInputField> {
+        self.fields.iter().filter(|f| f.computed.is_none())
+    }
+
+    // Errors from `try_transform` setters are kept in the builder until `build()` reports them
+    fn has_fallible_setters(&self) -> bool {
+        self.builder_fields()
+            .any(|f| f.setter.as_ref().is_some_and(|setter| setter.fallible))
+    }
+
+    // Initial values of every field in a fresh builder
+    fn builder_inits(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut inits: Vec<_> = self.builder_fields().map(|f| f.builder_init()).collect();
+        if self.has_fallible_setters() {
+            inits.push(quote! { __setter_errors: std::vec::Vec::new() });
+        }
+        if self.computed_marker().is_some() {
+            inits.push(quote! { __computed: core::marker::PhantomData });
+        }
+        inits
+    }
+
+    // The builder and partial structs declare every generic parameter of the struct but leave
+    // computed fields out. When some parameter is only used by computed fields, their types
+    // are kept in a `PhantomData` so the parameter is still used.
+    fn computed_marker(&self) -> Option<proc_macro2::TokenStream> {
+        let params: Vec<&syn::Ident> = self
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .chain(self.generics.lifetimes().map(|param| &param.lifetime.ident))
+            .collect();
+        let mut staged = vec![];
+        for f in self.builder_fields() {
+            collect_idents(f.field.ty.to_token_stream(), &mut staged);
+        }
+        let unused: Vec<&syn::Ident> = params
+            .into_iter()
+            .filter(|param| !staged.contains(param))
+            .collect();
+        let tys: Vec<&syn::Type> = self
+            .fields
+            .iter()
+            .filter(|f| f.computed.is_some())
+            .map(|f| &f.field.ty)
+            .filter(|ty| {
+                let mut idents = vec![];
+                collect_idents(ty.to_token_stream(), &mut idents);
+                idents.iter().any(|ident| unused.contains(&ident))
+            })
+            .collect();
+        if tys.is_empty() {
+            None
+        } else {
+            Some(quote! { core::marker::PhantomData<(#(#tys,)*)> })
+        }
+    }
+
+    // `let` bindings for the computed fields, evaluated after every other field is bound
+    fn computed_bindings(&self) -> proc_macro2::TokenStream {
+        let recurse = self.computed_order.iter().map(|&i| {
+            let f = &self.fields[i];
+            let name = &f.field.ident;
+            let ty = &f.field.ty;
+            let expr = f.computed.as_ref().expect("computed field");
+            quote_spanned! { expr.span() =>
+                let #name: #ty = #expr;
+            }
+        });
+        quote! {
+            #(#recurse)*
+        }
+    }
+
+    // `#ty: #bound` for the type of every field in `fields` that refers to one of the type
+    // parameters of the struct. Fields with concrete types don't need a bound.
+    fn field_bounds<'a>(
+        &self,
+        fields: impl Iterator<Item = &'a BuilderInputField>,
+        bound: proc_macro2::TokenStream,
+    ) -> Vec<syn::WherePredicate> {
+        let type_params: Vec<&syn::Ident> = self
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect();
+        fields
+            .filter(|f| {
+                let mut idents = vec![];
+                collect_idents(f.field.ty.to_token_stream(), &mut idents);
+                idents.iter().any(|ident| type_params.contains(&ident))
+            })
+            .map(|f| {
+                let ty = &f.field.ty;
+                syn::parse_quote!(#ty: #bound)
+            })
+            .collect()
+    }
+
+    // A method level where clause for `field_bounds`, empty when there aren't any bounds
+    fn method_where_clause(&self, bound: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let predicates = self.field_bounds(self.builder_fields(), bound);
+        if predicates.is_empty() {
+            quote! {}
+        } else {
+            quote! { where #(#predicates),* }
+        }
+    }
+
+    // The visibility of the builder struct and of `builder()`. The methods on the builder are
+    // all `pub`, so the builder can be used anywhere it can be named.
+    fn builder_vis(&self) -> &syn::Visibility {
+        self.attr.vis.as_ref().unwrap_or(&self.vis)
+    }
+
     pub fn builder_struct_ident(&self) -> syn::Ident {
         syn::Ident::new(
             &format!("{}Builder", self.ident),
@@ -102,76 +469,147 @@ impl BuilderInput {
         // The declaration of the struct, ie. it's visibility, type and identifier.
         // Made a variable as it's used in the different struct types
 
-        let vis = &self.vis;
+        let vis = self.builder_vis();
         let builder_name = self.builder_struct_ident();
-        let builder_decl = quote! {#vis struct #builder_name};
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
+        let builder_decl = quote! {#vis struct #builder_name #generics #where_clause};
 
-        let recurse = self.fields.pairs().map(|pair| {
-            let f = pair.value();
+        // With `#[builder(serde)]` any field can be missing from the input, a required field
+        // that's missing is then reported by `build()` like any other unset field
+        let serde = self.attr.serde;
+        let serde_derive = serde.then(|| quote! { #[derive(::serde::Deserialize)] });
+        let serde_default = serde.then(|| quote! { #[serde(default)] });
+        let serde_skip = serde.then(|| quote! { #[serde(skip)] });
+
+        let recurse = self.builder_fields().map(|f| {
             let name = &f.field.ident;
-            let ty = &f.field.ty;
-            if f.optional.is_some() || f.each.is_some() {
-                quote! {
-                    #name: #ty
-                }
-            } else {
-                quote! {
-                    #name: std::option::Option<#ty>
-                }
+            let ty = f.builder_ty();
+            quote! {
+                #serde_default
+                #name: #ty
+            }
+        });
+        let setter_errors = self.has_fallible_setters().then(|| {
+            quote! {
+                #serde_skip
+                __setter_errors: std::vec::Vec<(&'static str, std::sync::Arc<dyn std::error::Error + std::marker::Send + std::marker::Sync>)>,
+            }
+        });
+        let computed = self.computed_marker().map(|marker| {
+            quote! {
+                #serde_skip
+                __computed: #marker,
             }
         });
 
         quote! {
+            #serde_derive
             #builder_decl {
-                #(#recurse),*
+                #(#recurse,)*
+                #setter_errors
+                #computed
             }
         }
     }
     pub fn generate_builder_method(&self) -> proc_macro2::TokenStream {
-        let vis = &self.vis;
+        let vis = self.builder_vis();
         let builder_name = self.builder_struct_ident();
-        let recurse = self.fields.pairs().map(|pair| {
-            let f = pair.value();
-            let name = &f.field.ident;
-            if f.each.is_some() {
-                quote_spanned! { f.field.span() =>
-                    #name: std::vec::Vec::new()
-                }
-            } else {
-                quote! {
-                    #name: None
-                }
-            }
-        });
+        let recurse = self.builder_inits();
+        let const_fn = &self.attr.const_fn;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
         quote! {
-            #vis fn builder() -> #builder_name {
+            #vis #const_fn fn builder() -> #builder_name #ty_generics {
                 #builder_name {#(#recurse),*}
             }
         }
     }
     pub fn generate_setter_methods(&self) -> proc_macro2::TokenStream {
-        let recurse = self.fields.pairs().map(|pair| {
-            let f = pair.value();
-            if let Some(each) = &f.each {
-                let field_name = &f.field.ident;
-                let name = syn::Ident::new(&each.val, f.field.span());
-                let ty = &each.ty;
-                quote_spanned! { f.field.span() =>
-                    fn #name(&mut self, #name: #ty) -> &mut Self {
-                        self.#field_name.push(#name);
+        let recurse = self.builder_fields().map(|f| {
+            let field_name = f.field.ident.as_ref().expect("named field");
+            // The name of the setter and the type of the value it stores
+            let (name, ty) = if let Some(each) = &f.each {
+                (each.val.clone(), &each.ty)
+            } else {
+                (
+                    field_name.clone(),
+                    f.optional.as_ref().unwrap_or(&f.field.ty),
+                )
+            };
+            let store = if f.each.is_some() {
+                quote! { self.#field_name.push(#name); }
+            } else {
+                quote! { self.#field_name = std::option::Option::Some(#name); }
+            };
+            if self.attr.const_fn.is_some() {
+                // Setters take the builder by value, a const fn can't go through `&mut self`.
+                // Nor can it drop the value being replaced, it's wrapped in `ManuallyDrop`
+                // instead, which only leaks anything when a setter is called twice
+                return quote_spanned! { f.field.span() =>
+                    pub const fn #name(mut self, #name: #ty) -> Self {
+                        let _ = core::mem::ManuallyDrop::new(core::mem::replace(
+                            &mut self.#field_name,
+                            std::option::Option::Some(#name),
+                        ));
+                        self
+                    }
+                };
+            }
+            let Some(setter) = &f.setter else {
+                return quote_spanned! { f.field.span() =>
+                    pub fn #name(&mut self, #name: #ty) -> &mut Self {
+                        #store
+                        self
+                    }
+                };
+            };
+
+            let closure = &setter.closure;
+            let args: Vec<syn::Ident> = closure
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, input)| match input {
+                    syn::Pat::Type(syn::PatType { pat, .. }) => match &**pat {
+                        syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                        _ => format_ident!("arg{}", i),
+                    },
+                    _ => format_ident!("arg{}", i),
+                })
+                .collect();
+            let arg_tys = closure.inputs.iter().map(|input| match input {
+                syn::Pat::Type(pat_type) => &*pat_type.ty,
+                _ => unreachable!("checked while parsing"),
+            });
+            if setter.fallible {
+                let field_str = field_name.unraw().to_string();
+                // A repeated field keeps the errors of earlier elements, a single value that's
+                // set again replaces the error of the value before it
+                let retain = f.each.is_none().then(|| {
+                    quote! {
+                        self.__setter_errors.retain(|(field, _)| *field != #field_str);
+                    }
+                });
+                quote_spanned! { closure.span() =>
+                    pub fn #name(&mut self, #(#args: #arg_tys),*) -> &mut Self {
+                        #retain
+                        match (#closure)(#(#args),*) {
+                            core::result::Result::Ok(#name) => {
+                                #store
+                            }
+                            core::result::Result::Err(err) => {
+                                let err: std::boxed::Box<dyn std::error::Error + std::marker::Send + std::marker::Sync> = std::convert::Into::into(err);
+                                self.__setter_errors.push((#field_str, std::sync::Arc::from(err)));
+                            }
+                        }
                         self
                     }
                 }
             } else {
-                let name = &f.field.ident;
-                let ty = if f.optional.is_some() {
-                    f.optional.as_ref().unwrap()
-                } else {
-                    &f.field.ty
-                };
-                quote! {
-                    fn #name(&mut self, #name: #ty) -> &mut Self {
-                        self.#name = Some(#name);
+                quote_spanned! { closure.span() =>
+                    pub fn #name(&mut self, #(#args: #arg_tys),*) -> &mut Self {
+                        let #name: #ty = (#closure)(#(#args),*);
+                        #store
                         self
                     }
                 }
@@ -182,19 +620,57 @@ impl BuilderInput {
         }
     }
 
+    // `clear_<field>` and `take_<field>` for every field, and `reset` for the whole builder.
+    // All of them put the fields back into the state `builder()` creates them in.
+    pub fn generate_reset_methods(&self) -> proc_macro2::TokenStream {
+        let builder_name = self.builder_struct_ident();
+        let recurse = self.builder_fields().map(|f| {
+            let name = f.field.ident.as_ref().expect("named field");
+            let clear_name = format_ident!("clear_{}", name);
+            let take_name = format_ident!("take_{}", name);
+            let ty = f.builder_ty();
+            let clear_errors = f.setter.as_ref().filter(|setter| setter.fallible).map(|_| {
+                let name_str = name.unraw().to_string();
+                quote! {
+                    self.__setter_errors.retain(|(field, _)| *field != #name_str);
+                }
+            });
+            quote_spanned! { f.field.span() =>
+                pub fn #clear_name(&mut self) -> &mut Self {
+                    self.#name = std::default::Default::default();
+                    #clear_errors
+                    self
+                }
+
+                pub fn #take_name(&mut self) -> #ty {
+                    std::mem::take(&mut self.#name)
+                }
+            }
+        });
+        let inits = self.builder_inits();
+        quote! {
+            #(#recurse)*
+
+            pub fn reset(&mut self) -> &mut Self {
+                *self = #builder_name {#(#inits),*};
+                self
+            }
+        }
+    }
+
     pub fn generate_final_build_method(&self) -> proc_macro2::TokenStream {
         let field_check = {
-            let recurse = self.fields.pairs().map(|pair| {
-                let f = pair.value();
+            let recurse = self.builder_fields().map(|f| {
                 let field = &f.field.ident;
                 if f.optional.is_some() || f.each.is_some() {
                     quote! {
-                        let #field = Clone::clone(&self.#field);
+                        let #field = std::clone::Clone::clone(&self.#field);
                     }
                 } else {
+                    let message = format!("missing field `{}`", field.as_ref().expect("named field").unraw());
                     quote! {
-                        let Some(#field) = Clone::clone(&self.#field) else {
-                            return Err(String::from("missing #field_name value").into());
+                        let std::option::Option::Some(#field) = std::clone::Clone::clone(&self.#field) else {
+                            return core::result::Result::Err(std::convert::From::from(#message));
                         };
                     }
                 }
@@ -203,32 +679,491 @@ impl BuilderInput {
                 #(#recurse)*
             }
         };
-        let field_set = {
-            let recurse = self.fields.pairs().map(|pair| {
-                let f = pair.value();
-                let field = &f.field.ident;
-                quote_spanned! {f.field.span() =>
-                    #field: Clone::clone(&#field)
-                }
-            });
+        // Every binding is already owned, they're moved into the struct
+        let field_set = self.fields.iter().map(|f| &f.field.ident);
+        // The errors stay on the builder so every `build()` reports them, the one returned wraps
+        // the first and hands it out as its `source()`
+        let setter_errors = self.has_fallible_setters().then(|| {
             quote! {
-                #(#recurse),*
+                if let std::option::Option::Some((field, err)) = self.__setter_errors.first() {
+                    #[derive(Debug)]
+                    struct SetterError(&'static str, std::sync::Arc<dyn std::error::Error + std::marker::Send + std::marker::Sync>);
+
+                    impl std::fmt::Display for SetterError {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            std::write!(f, "invalid value for `{}`: {}", self.0, self.1)
+                        }
+                    }
+
+                    impl std::error::Error for SetterError {
+                        fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)> {
+                            std::option::Option::Some(&*self.1)
+                        }
+                    }
+
+                    return core::result::Result::Err(std::boxed::Box::new(SetterError(
+                        field,
+                        std::sync::Arc::clone(err),
+                    )));
+                }
             }
-        };
+        });
+        let computed = self.computed_bindings();
         let struct_name = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause = self.method_where_clause(quote! { std::clone::Clone });
         quote! {
-            pub fn build(&mut self) -> core::result::Result<#struct_name, std::boxed::Box<dyn std::error::Error>> {
+            pub fn build(&mut self) -> core::result::Result<#struct_name #ty_generics, std::boxed::Box<dyn std::error::Error>>
+            #where_clause
+            {
+                #setter_errors
                 #field_check
+                #computed
 
-                Ok(#struct_name {
-                    #field_set
+                core::result::Result::Ok(#struct_name {
+                    #(#field_set),*
                 })
             }
         }
     }
+
+    // Consumes the builder so no field needs to be cloned, which isn't possible in a const fn.
+    // A missing field panics, which fails compilation when evaluated in a const context.
+    pub fn generate_const_build_method(&self) -> proc_macro2::TokenStream {
+        if self.attr.const_fn.is_none() {
+            return quote! {};
+        }
+        // Moving the fields out of the builder would drop what's left of it, which a const fn
+        // can't do for fields with destructors. They're taken, leaving `None` behind, and the
+        // builder is forgotten
+        let take = self.builder_fields().map(|f| {
+            let name = &f.field.ident;
+            quote! {
+                let #name = self.#name.take();
+            }
+        });
+        let field_check = self.builder_fields().map(|f| {
+            let name = f.field.ident.as_ref().expect("named field");
+            if f.optional.is_some() {
+                quote! {}
+            } else {
+                let message = format!("missing field `{}`", name.unraw());
+                quote_spanned! { f.field.span() =>
+                    let #name = #name.expect(#message);
+                }
+            }
+        });
+        let computed = self.computed_bindings();
+        let field_set = self.fields.iter().map(|f| &f.field.ident);
+        let struct_name = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote! {
+            pub const fn build_const(mut self) -> #struct_name #ty_generics {
+                #(#take)*
+                core::mem::forget(self);
+                #(#field_check)*
+                #computed
+
+                #struct_name {
+                    #(#field_set),*
+                }
+            }
+        }
+    }
+
+    // `build_boxed`, `build_rc` and `build_arc` all go through `build()`, so they fail in
+    // exactly the same way it does.
+    pub fn generate_wrapper_build_methods(&self) -> proc_macro2::TokenStream {
+        let struct_name = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause = self.method_where_clause(quote! { std::clone::Clone });
+        let recurse = self.attr.build_fn.wrappers.iter().map(|wrapper| {
+            let (method, pointer) = match wrapper {
+                BuildWrapper::Box => (quote! { build_boxed }, quote! { std::boxed::Box }),
+                BuildWrapper::Rc => (quote! { build_rc }, quote! { std::rc::Rc }),
+                BuildWrapper::Arc => (quote! { build_arc }, quote! { std::sync::Arc }),
+            };
+            quote! {
+                pub fn #method(&mut self) -> core::result::Result<#pointer<#struct_name #ty_generics>, std::boxed::Box<dyn std::error::Error>>
+                #where_clause
+                {
+                    self.build().map(#pointer::new)
+                }
+            }
+        });
+        quote! {
+            #(#recurse)*
+        }
+    }
+
+    pub fn partial_struct_ident(&self) -> syn::Ident {
+        syn::Ident::new(
+            &format!("{}Partial", self.ident),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    // Every field of the partial struct is an Option, so it can be used as a patch where
+    // `None` means the field was never set on the builder.
+    pub fn generate_partial_struct(&self) -> proc_macro2::TokenStream {
+        if !self.attr.build_fn.partial {
+            return quote! {};
+        }
+        // `build_partial` is as visible as the builder, so is what it returns
+        let vis = self.builder_vis();
+        let partial_name = self.partial_struct_ident();
+        let recurse = self.builder_fields().map(|f| {
+            let field_vis = &f.field.vis;
+            let name = &f.field.ident;
+            let ty = f.optional.as_ref().unwrap_or(&f.field.ty);
+            quote_spanned! { f.field.span() =>
+                #field_vis #name: std::option::Option<#ty>
+            }
+        });
+        let computed = self.computed_marker().map(|marker| {
+            quote! {
+                __computed: #marker,
+            }
+        });
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
+        quote! {
+            #vis struct #partial_name #generics #where_clause {
+                #(#recurse,)*
+                #computed
+            }
+        }
+    }
+
+    pub fn generate_partial_build_method(&self) -> proc_macro2::TokenStream {
+        if !self.attr.build_fn.partial {
+            return quote! {};
+        }
+        let partial_name = self.partial_struct_ident();
+        let recurse = self.builder_fields().map(|f| {
+            let name = &f.field.ident;
+            if f.each.is_some() {
+                quote! {
+                    #name: if self.#name.is_empty() {
+                        std::option::Option::None
+                    } else {
+                        std::option::Option::Some(std::clone::Clone::clone(&self.#name))
+                    }
+                }
+            } else {
+                quote! {
+                    #name: std::clone::Clone::clone(&self.#name)
+                }
+            }
+        });
+        let computed = self
+            .computed_marker()
+            .map(|_| quote! { __computed: core::marker::PhantomData, });
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause = self.method_where_clause(quote! { std::clone::Clone });
+        quote! {
+            pub fn build_partial(&self) -> #partial_name #ty_generics
+            #where_clause
+            {
+                #partial_name {
+                    #(#recurse,)*
+                    #computed
+                }
+            }
+        }
+    }
+
+    // Never fails, any required field that wasn't set is filled with `Default::default()`
+    pub fn generate_or_default_build_method(&self) -> proc_macro2::TokenStream {
+        if !self.attr.build_fn.or_default {
+            return quote! {};
+        }
+        let recurse = self.builder_fields().map(|f| {
+            let name = &f.field.ident;
+            if f.optional.is_some() || f.each.is_some() {
+                quote! {
+                    let #name = std::clone::Clone::clone(&self.#name);
+                }
+            } else {
+                quote_spanned! { f.field.span() =>
+                    let #name = std::clone::Clone::clone(&self.#name).unwrap_or_default();
+                }
+            }
+        });
+        let computed = self.computed_bindings();
+        let field_set = self.fields.iter().map(|f| &f.field.ident);
+        let struct_name = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause =
+            self.method_where_clause(quote! { std::clone::Clone + std::default::Default });
+        quote! {
+            pub fn build_or_default(&mut self) -> #struct_name #ty_generics
+            #where_clause
+            {
+                #(#recurse)*
+                #computed
+
+                #struct_name {
+                    #(#field_set),*
+                }
+            }
+        }
+    }
+
+    // Only generated when the struct is marked `#[builder(debug)]`. Fields that haven't been
+    // set are printed as `<unset>`, fields marked `#[builder(redact)]` never print their value.
+    pub fn generate_debug_impl(&self) -> proc_macro2::TokenStream {
+        if !self.attr.debug {
+            return quote! {};
+        }
+        let builder_name = self.builder_struct_ident();
+        let builder_name_str = builder_name.to_string();
+        let recurse = self.builder_fields().map(|f| {
+            let name = &f.field.ident;
+            let name_str = name.as_ref().expect("named field").unraw().to_string();
+            let (binding, value) = if f.redact {
+                (quote! { _ }, quote! { &format_args!("<redacted>") })
+            } else {
+                (quote! { #name }, quote! { #name })
+            };
+            if f.each.is_some() {
+                quote! {
+                    let #binding = &self.#name;
+                    debug.field(#name_str, #value);
+                }
+            } else {
+                quote! {
+                    match &self.#name {
+                        std::option::Option::Some(#binding) => debug.field(#name_str, #value),
+                        std::option::Option::None => debug.field(#name_str, &format_args!("<unset>")),
+                    };
+                }
+            }
+        });
+        let mut generics = self.generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(self.field_bounds(
+                self.builder_fields().filter(|f| !f.redact),
+                quote! { std::fmt::Debug },
+            ));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics std::fmt::Debug for #builder_name #ty_generics #where_clause {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    let mut debug = f.debug_struct(#builder_name_str);
+                    #(#recurse)*
+                    debug.finish()
+                }
+            }
+        }
+    }
 }
+
+// Repeated fields and setter transforms can't be used from a const fn
+fn const_builder_errors<'a>(
+    const_fn: &'a Token![const],
+    fields: &'a syn::punctuated::Punctuated<BuilderInputField, Token![,]>,
+) -> impl Iterator<Item = syn::Error> + 'a {
+    fields.iter().filter_map(move |f| {
+        let unsupported = if f.each.is_some() {
+            "`each`"
+        } else if f.setter.is_some() {
+            "setter transforms"
+        } else {
+            return None;
+        };
+        let mut err = syn::Error::new(
+            f.field.span(),
+            format!("{unsupported} can't be used with `builder(const)`"),
+        );
+        err.combine(syn::Error::new(
+            const_fn.span,
+            "`builder(const)` given here",
+        ));
+        Some(err)
+    })
+}
+
+// Methods the builder always has, or may have depending on `build_fn(...)` and `const`
+const BUILDER_METHODS: &[&str] = &[
+    "build",
+    "build_const",
+    "build_partial",
+    "build_or_default",
+    "build_boxed",
+    "build_rc",
+    "build_arc",
+    "reset",
+];
+
+// Two fields whose generated methods share a name, ie. an `each` setter named after another
+// field, would otherwise fail with a duplicate definition error pointing at the derive
+fn method_name_conflicts(
+    fields: &syn::punctuated::Punctuated<BuilderInputField, Token![,]>,
+) -> Vec<syn::Error> {
+    let mut errors = vec![];
+    let mut methods: Vec<(String, &syn::Ident)> = vec![];
+    for f in fields.iter().filter(|f| f.computed.is_none()) {
+        let field_name = f.field.ident.as_ref().expect("named field");
+        let mut names = vec![
+            (field_name.unraw().to_string(), field_name.span()),
+            (format!("clear_{}", field_name.unraw()), field_name.span()),
+            (format!("take_{}", field_name.unraw()), field_name.span()),
+        ];
+        if let Some(each) = &f.each {
+            if each.val.unraw() != field_name.unraw() {
+                names.push((each.val.unraw().to_string(), each.val.span()));
+            }
+        }
+        for (name, span) in names {
+            if BUILDER_METHODS.contains(&name.as_str()) {
+                errors.push(syn::Error::new(
+                    span,
+                    format!("method `{name}` of field `{field_name}` conflicts with the builder's own `{name}` method"),
+                ));
+            } else if let Some((_, other)) = methods.iter().find(|(other, _)| *other == name) {
+                errors.push(syn::Error::new(
+                    span,
+                    format!("method `{name}` of field `{field_name}` conflicts with the one generated for field `{other}`"),
+                ));
+            } else {
+                methods.push((name, field_name));
+            }
+        }
+    }
+    errors
+}
+
+fn combine_errors(errors: Vec<syn::Error>) -> Option<syn::Error> {
+    errors.into_iter().reduce(|mut combined, err| {
+        combined.combine(err);
+        combined
+    })
+}
+
+// Error for a key that isn't one of `known`, suggesting the closest known key if there is one
+fn unknown_key_error(
+    meta: &syn::meta::ParseNestedMeta,
+    attribute: &str,
+    known: &[&str],
+) -> syn::Error {
+    let key = meta.path.to_token_stream().to_string().replace(' ', "");
+    let suggestion = known
+        .iter()
+        .map(|candidate| (edit_distance(&key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= key.len().max(3) / 3)
+        .min_by_key(|(distance, _)| *distance);
+    let message = if let Some((_, suggestion)) = suggestion {
+        format!("unknown `{attribute}` attribute `{key}`, did you mean `{suggestion}`?")
+    } else {
+        let known: Vec<String> = known.iter().map(|k| format!("`{k}`")).collect();
+        format!(
+            "unknown `{attribute}` attribute `{key}`, expected one of {}",
+            known.join(", ")
+        )
+    };
+    syn::Error::new_spanned(&meta.path, message)
+}
+
+// Consumes the `= value` or `(...)` following an unknown key so the keys after it can still
+// be parsed
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::Group>()?;
+    }
+    Ok(())
+}
+
+// Levenshtein distance between two keys
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// Orders the computed fields so that every computed field is evaluated after the computed
+// fields its expression refers to. A cycle between computed fields can't be ordered.
+fn computed_order(
+    fields: &syn::punctuated::Punctuated<BuilderInputField, Token![,]>,
+) -> syn::Result<Vec<usize>> {
+    let computed: Vec<usize> = (0..fields.len())
+        .filter(|&i| fields[i].computed.is_some())
+        .collect();
+    // For every computed field, the computed fields it refers to
+    let mut dependencies: Vec<(usize, Vec<usize>)> = computed
+        .iter()
+        .map(|&i| {
+            let mut idents = vec![];
+            collect_idents(
+                fields[i]
+                    .computed
+                    .as_ref()
+                    .expect("computed field")
+                    .to_token_stream(),
+                &mut idents,
+            );
+            let depends_on = computed
+                .iter()
+                .copied()
+                .filter(|&j| {
+                    let name = fields[j].field.ident.as_ref();
+                    idents.iter().any(|ident| Some(ident) == name)
+                })
+                .collect();
+            (i, depends_on)
+        })
+        .collect();
+
+    let mut order = vec![];
+    while !dependencies.is_empty() {
+        let Some(pos) = dependencies
+            .iter()
+            .position(|(_, depends_on)| depends_on.iter().all(|j| order.contains(j)))
+        else {
+            let names: Vec<String> = dependencies
+                .iter()
+                .map(|(i, _)| {
+                    format!(
+                        "`{}`",
+                        fields[*i].field.ident.as_ref().expect("named field")
+                    )
+                })
+                .collect();
+            let (first, _) = dependencies[0];
+            return Err(syn::Error::new(
+                fields[first].computed.span(),
+                format!("computed fields {} depend on each other", names.join(", ")),
+            ));
+        };
+        order.push(dependencies.remove(pos).0);
+    }
+    Ok(order)
+}
+
+fn collect_idents(tokens: proc_macro2::TokenStream, idents: &mut Vec<syn::Ident>) {
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Ident(ident) => idents.push(ident),
+            proc_macro2::TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
 // Checks if a field is a container against an iter of possible path types with only one colon separator,
-// like "Option:", "std:option:Option" or "core:option:Option".
+// like "Option:", "std:option:Option:" or "core:option:Option:".
 // Then will return a Some(syn::Type), syn::Type=the contained type
 fn type_confirm(
     mut pattern: impl Iterator<Item = &'static str>,
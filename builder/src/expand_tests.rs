@@ -0,0 +1,66 @@
+// Snapshot tests of the expanded builder code. Every `tests/expand/*.rs` file holds a struct
+// the derive is run on, the pretty printed expansion is compared against the matching
+// `*.expanded.rs` file. Run with `SNAPSHOTS=overwrite` to accept changed expansions.
+
+use std::fs;
+use std::path::Path;
+
+use crate::builder_input::BuilderInput;
+
+#[test]
+fn expand() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand");
+    let overwrite = std::env::var("SNAPSHOTS").is_ok_and(|var| var == "overwrite");
+
+    let mut inputs: Vec<_> = fs::read_dir(&dir)
+        .expect("tests/expand directory")
+        .map(|entry| entry.expect("directory entry").path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.ends_with(".rs") && !name.ends_with(".expanded.rs")
+        })
+        .collect();
+    inputs.sort();
+    assert!(!inputs.is_empty(), "no inputs in {}", dir.display());
+
+    let mut mismatches = vec![];
+    for input in inputs {
+        let source = fs::read_to_string(&input).unwrap();
+        let builder_input: BuilderInput =
+            syn::parse_str(&source).unwrap_or_else(|err| panic!("{}: {}", input.display(), err));
+        let file: syn::File = syn::parse2(crate::expand(&builder_input))
+            .unwrap_or_else(|err| panic!("{}: {}", input.display(), err));
+        let expanded = prettyplease::unparse(&file);
+
+        let snapshot = input.with_extension("expanded.rs");
+        match fs::read_to_string(&snapshot) {
+            Ok(expected) if expected == expanded => {}
+            Ok(_) | Err(_) if overwrite => fs::write(&snapshot, expanded).unwrap(),
+            Ok(expected) => mismatches.push(format!(
+                "{} doesn't match the expansion:\n{}",
+                snapshot.display(),
+                diff(&expected, &expanded),
+            )),
+            Err(_) => {
+                fs::write(&snapshot, expanded).unwrap();
+                mismatches.push(format!("{} was missing, review it", snapshot.display()));
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n\n"));
+}
+
+// Lines that only exist on one side, good enough to spot what changed
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let removed = expected
+        .iter()
+        .filter(|line| !actual.contains(line))
+        .map(|line| format!("- {line}"));
+    let added = actual
+        .iter()
+        .filter(|line| !expected.contains(line))
+        .map(|line| format!("+ {line}"));
+    removed.chain(added).collect::<Vec<_>>().join("\n")
+}
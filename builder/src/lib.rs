@@ -5,12 +5,19 @@ use syn::parse_macro_input;
 use crate::builder_input::BuilderInput;
 
 mod builder_input;
+#[cfg(test)]
+mod expand_tests;
 
 #[proc_macro_derive(Builder, attributes(builder))]
 pub fn derive(input: TokenStream) -> TokenStream {
     // eprintln!("INPUT: {}", input);
     let input = parse_macro_input!(input as BuilderInput);
 
+    proc_macro::TokenStream::from(expand(&input))
+}
+
+// Split out of `derive` so the expansion can be tested without going through the compiler
+fn expand(input: &BuilderInput) -> proc_macro2::TokenStream {
     let struct_name = &input.ident;
 
     let builder_name = &input.builder_struct_ident();
@@ -21,24 +28,47 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     let builder_setter_methods = input.generate_setter_methods();
 
+    let builder_reset_methods = input.generate_reset_methods();
+
     let builder_final_method = input.generate_final_build_method();
 
+    let builder_const_method = input.generate_const_build_method();
+
+    let builder_wrapper_methods = input.generate_wrapper_build_methods();
+
+    let builder_partial_method = input.generate_partial_build_method();
+
+    let builder_or_default_method = input.generate_or_default_build_method();
+
+    let builder_debug_impl = input.generate_debug_impl();
+
+    let partial_struct = input.generate_partial_struct();
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
     let derive_impl = quote! {
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
             #builder_method
         }
 
-        impl #builder_name {
+        impl #impl_generics #builder_name #ty_generics #where_clause {
             #builder_setter_methods
+            #builder_reset_methods
             #builder_final_method
+            #builder_const_method
+            #builder_wrapper_methods
+            #builder_partial_method
+            #builder_or_default_method
         }
     };
 
     let stream = quote! {
         #builder_struct
+        #partial_struct
         #derive_impl
+        #builder_debug_impl
     };
     // eprintln!("STREAM: {}", stream);
 
-    proc_macro::TokenStream::from(stream)
+    stream
 }
@@ -0,0 +1,43 @@
+// Generate a Debug impl for the builder when the struct is annotated with
+// #[builder(debug)]. This makes it possible to log a half-built builder while
+// troubleshooting a configuration.
+//
+// Every field is printed with its staged value, or `<unset>` when no value has
+// been given yet. Fields containing secrets can be marked #[builder(redact)] so
+// that only `<redacted>` is ever printed for them.
+//
+//     impl std::fmt::Debug for CommandBuilder {
+//         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//             ...
+//         }
+//     }
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(debug)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(redact)]
+    token: String,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    assert_eq!(
+        format!("{:?}", builder),
+        r#"CommandBuilder { executable: <unset>, args: [], current_dir: <unset>, token: <unset> }"#,
+    );
+
+    builder
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .token("hunter2".to_owned());
+    assert_eq!(
+        format!("{:?}", builder),
+        r#"CommandBuilder { executable: "cargo", args: ["build"], current_dir: <unset>, token: <redacted> }"#,
+    );
+}
@@ -0,0 +1,48 @@
+// Support PATCH-style updates with the same derive. With
+// #[builder(build_fn(partial))] the macro generates a `CommandPartial` struct
+// where every field is an Option, along with a `build_partial` method that
+// never fails and reports unset fields as None.
+//
+// With #[builder(build_fn(or_default))] a `build_or_default` method is
+// generated which fills any missing required field from `Default::default()`.
+//
+//     pub struct CommandPartial {
+//         executable: Option<String>,
+//         args: Option<Vec<String>>,
+//         current_dir: Option<String>,
+//     }
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(build_fn(partial, or_default))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    retries: u32,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cargo".to_owned());
+
+    let partial: CommandPartial = builder.build_partial();
+    assert_eq!(partial.executable.as_deref(), Some("cargo"));
+    assert!(partial.args.is_none());
+    assert!(partial.current_dir.is_none());
+    assert!(partial.retries.is_none());
+
+    builder.arg("build".to_owned());
+    let partial = builder.build_partial();
+    assert_eq!(partial.args, Some(vec!["build".to_owned()]));
+
+    let command: Command = builder.build_or_default();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir, None);
+    assert_eq!(command.retries, 0);
+
+    assert!(builder.build().is_err());
+}
@@ -0,0 +1,46 @@
+// Long-lived builders get reused across requests, so every field gets a
+// `clear_<field>` method that puts it back to unset (None or an empty Vec) and
+// a `take_<field>` method that hands back the staged value while clearing it.
+// A `reset` method clears the whole builder at once.
+//
+//     impl CommandBuilder {
+//         pub fn clear_executable(&mut self) -> &mut Self {...}
+//         pub fn take_executable(&mut self) -> Option<String> {...}
+//         pub fn reset(&mut self) -> &mut Self {...}
+//     }
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .current_dir("..".to_owned());
+
+    assert_eq!(builder.take_executable(), Some("cargo".to_owned()));
+    assert_eq!(builder.take_executable(), None);
+    assert!(builder.build().is_err());
+
+    builder.executable("rustc".to_owned()).clear_args();
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "rustc");
+    assert!(command.args.is_empty());
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+
+    assert_eq!(builder.take_current_dir(), Some("..".to_owned()));
+    builder.arg("--help".to_owned());
+    assert_eq!(builder.take_args(), vec!["--help"]);
+
+    builder.arg("--help".to_owned()).reset();
+    assert_eq!(builder.take_args(), Vec::<String>::new());
+    assert!(builder.build().is_err());
+}
@@ -0,0 +1,38 @@
+// Some fields aren't set directly but derived from the other fields when the
+// struct is built. A field marked #[builder(computed = "...")] gets no setter;
+// instead the expression is evaluated in `build()`, where every other field is
+// already available as a local variable holding its resolved value.
+//
+// Computed fields can refer to other computed fields regardless of the order
+// they are declared in.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Endpoint {
+    #[builder(computed = "format!(\"{}/{}\", authority, path.as_deref().unwrap_or(\"\"))")]
+    url: String,
+    host: String,
+    port: u16,
+    #[builder(computed = "format!(\"{}:{}\", host, port)")]
+    authority: String,
+    path: Option<String>,
+}
+
+fn main() {
+    let endpoint = Endpoint::builder()
+        .host("localhost".to_owned())
+        .port(8080)
+        .build()
+        .unwrap();
+    assert_eq!(endpoint.authority, "localhost:8080");
+    assert_eq!(endpoint.url, "localhost:8080/");
+
+    let endpoint = Endpoint::builder()
+        .host("localhost".to_owned())
+        .port(8080)
+        .path("health".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(endpoint.url, "localhost:8080/health");
+}
@@ -0,0 +1,16 @@
+// Computed fields that depend on each other can never be evaluated, so the
+// macro reports the cycle instead of generating code that fails to compile with
+// a confusing error about an undeclared variable.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Rectangle {
+    #[builder(computed = "area / height")]
+    width: u32,
+    #[builder(computed = "width * height")]
+    area: u32,
+    height: u32,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Large structs are often wrapped in a smart pointer as soon as they're built.
+// With #[builder(build_fn(wrappers(...)))] the builder gets `build_boxed`,
+// `build_rc` and/or `build_arc` methods which construct straight into a Box, Rc
+// or Arc. They fail in exactly the same cases `build` does.
+
+use derive_builder::Builder;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Builder)]
+#[builder(build_fn(wrappers(arc, rc, box)))]
+pub struct Command {
+    executable: String,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    assert!(builder.build_arc().is_err());
+    assert!(builder.build_rc().is_err());
+    assert!(builder.build_boxed().is_err());
+
+    builder.executable("cargo".to_owned());
+    let command: Arc<Command> = builder.build_arc().unwrap();
+    assert_eq!(command.executable, "cargo");
+    let command: Rc<Command> = builder.build_rc().unwrap();
+    assert_eq!(command.executable, "cargo");
+    let command: Box<Command> = builder.build_boxed().unwrap();
+    assert!(command.current_dir.is_none());
+}
@@ -0,0 +1,20 @@
+// Every #[builder(...)] attribute on the struct and on every field is parsed
+// before anything is reported, so a single compile shows all of the mistakes
+// instead of stopping at the first one. Unknown keys come with a suggestion
+// when they look like a typo of a known key.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(debg, build_fn(partail, wrappers(arc, boxed)))]
+pub struct Command {
+    #[builder(redact)]
+    #[builder(redcat)]
+    executable: String,
+    #[builder(each = "arg", colour = "red")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: String,
+}
+
+fn main() {}
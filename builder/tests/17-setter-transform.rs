@@ -0,0 +1,69 @@
+// A setter normally takes exactly the type of its field. With
+// #[builder(setter(transform = |...| ...))] the setter instead takes the
+// closure's arguments and stores whatever the closure returns, which allows
+// accepting more convenient argument types.
+//
+// The fallible variant #[builder(setter(try_transform = |...| ...))] expects
+// the closure to return a Result. The setter still returns &mut Self so calls
+// can be chained; an error is held on to by the builder and returned from
+// every `build()`, wrapped in an error naming the field whose `source()` is
+// the closure's own error.
+
+use derive_builder::Builder;
+use std::net::Ipv4Addr;
+
+#[derive(Builder)]
+pub struct Server {
+    #[builder(setter(transform = |s: &str| s.parse::<Ipv4Addr>().unwrap()))]
+    address: Ipv4Addr,
+    #[builder(setter(try_transform = |port: &str| port.parse::<u16>()))]
+    port: u16,
+    #[builder(setter(transform = |name: &str| name.to_owned()))]
+    name: Option<String>,
+    #[builder(each = "header", setter(transform = |key: &str, value: &str| format!("{}: {}", key, value)))]
+    headers: Vec<String>,
+    #[builder(each = "code", setter(try_transform = |code: &str| code.parse::<u16>()))]
+    codes: Vec<u16>,
+}
+
+// The errors held on to are `Send`, the builder can still move between threads
+fn assert_send<T: Send>() {}
+
+fn main() {
+    assert_send::<ServerBuilder>();
+
+    let server = Server::builder()
+        .address("127.0.0.1")
+        .port("8080")
+        .name("local")
+        .header("Accept", "*/*")
+        .build()
+        .unwrap();
+    assert_eq!(server.address, Ipv4Addr::LOCALHOST);
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.name.as_deref(), Some("local"));
+    assert_eq!(server.headers, vec!["Accept: */*"]);
+
+    let mut builder = Server::builder();
+    builder.address("127.0.0.1").port("http");
+    let Err(err) = builder.build() else {
+        panic!("expected the port error");
+    };
+    assert!(err.to_string().starts_with("invalid value for `port`"));
+    let source = err.source().expect("the closure's error");
+    assert!(source.downcast_ref::<std::num::ParseIntError>().is_some());
+
+    // Building doesn't clear the error, a second attempt fails the same way
+    assert!(builder.build().is_err());
+
+    // Setting the field again replaces the error
+    builder.port("http").port("80");
+    assert_eq!(builder.build().unwrap().port, 80);
+
+    // An element of a repeated field that failed isn't forgotten either
+    let mut builder = Server::builder();
+    builder.address("127.0.0.1").port("80").code("x").code("y").code("3");
+    assert!(builder.build().is_err());
+    assert!(builder.build().is_err());
+    assert!(builder.build().is_err());
+}
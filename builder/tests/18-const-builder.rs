@@ -0,0 +1,42 @@
+// Static configuration tables can't call ordinary functions. With
+// #[builder(const)] the `builder()` function and the setters become const fns,
+// and a `build_const` method is generated that can be used to initialise
+// `const` and `static` items.
+//
+// A const fn can't take `&mut self`, so in this mode the setters take the
+// builder by value and return it. `build_const` panics on a missing field,
+// which fails compilation when it's evaluated in a const context.
+//
+//     impl PinBuilder {
+//         pub const fn number(mut self, number: u8) -> Self {...}
+//         pub const fn build_const(self) -> Pin {...}
+//     }
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Pin {
+    number: u8,
+    label: &'static str,
+    pull_up: Option<bool>,
+    #[builder(computed = "number as u32 * 4")]
+    offset: u32,
+}
+
+static PINS: [Pin; 2] = [
+    Pin::builder().number(1).label("led").build_const(),
+    Pin::builder().number(2).label("button").pull_up(true).build_const(),
+];
+
+fn main() {
+    assert_eq!(PINS[0].label, "led");
+    assert_eq!(PINS[0].pull_up, None);
+    assert_eq!(PINS[1].offset, 8);
+    assert_eq!(PINS[1].pull_up, Some(true));
+
+    // The regular build method is still available
+    let pin = Pin::builder().number(3).label("spare").build().unwrap();
+    assert_eq!(pin.number, 3);
+    assert!(Pin::builder().number(4).build().is_err());
+}
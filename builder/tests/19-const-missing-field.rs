@@ -0,0 +1,17 @@
+// A field that's never set is caught at compile time when `build_const` is
+// evaluated in a const context.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Pin {
+    number: u8,
+    label: &'static str,
+}
+
+const PIN: Pin = Pin::builder().number(1).build_const();
+
+fn main() {
+    let _ = PIN.label;
+}
@@ -0,0 +1,67 @@
+// With the `serde` feature of this crate enabled, #[builder(serde)] makes the
+// builder implement serde::Deserialize. Every field of the builder is optional in the input, so a
+// partial configuration can be deserialized into a builder, overridden from
+// code through the setters and then validated by `build()`.
+//
+// A required field that's missing from the input isn't a deserialization
+// error, it's reported by `build()` like any other field that was never set.
+//
+// The feature is shared by every crate in the build, so builders of structs
+// without the attribute are left alone, their fields don't need to implement
+// Deserialize.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(serde)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct Handle;
+
+#[derive(Builder)]
+pub struct Process {
+    handle: Handle,
+}
+
+// Computed fields aren't part of the input, a type parameter only they use
+// doesn't need to implement Deserialize either
+#[derive(Default)]
+pub struct Tag;
+
+#[derive(Builder)]
+#[builder(serde)]
+pub struct Tagged<T: Default> {
+    name: String,
+    #[builder(computed = "T::default()")]
+    tag: T,
+}
+
+fn main() {
+    let mut builder: CommandBuilder =
+        serde_json::from_str(r#"{ "args": ["build"], "current_dir": ".." }"#).unwrap();
+    assert!(builder.build().is_err());
+
+    let command = builder
+        .executable("cargo".to_owned())
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+
+    let mut builder: CommandBuilder = serde_json::from_str("{}").unwrap();
+    assert!(builder.executable("cargo".to_owned()).build().is_ok());
+
+    assert!(Process::builder().handle(Handle).build().is_ok());
+
+    let mut builder: TaggedBuilder<Tag> = serde_json::from_str(r#"{ "name": "t" }"#).unwrap();
+    let Tagged { name, tag: Tag } = builder.build().unwrap();
+    assert_eq!(name, "t");
+}
@@ -0,0 +1,74 @@
+// A #[non_exhaustive] struct with private fields can't be constructed outside
+// of the module (or crate) that defines it, which is exactly the case a
+// builder should solve. The builder and all of its methods are public wherever
+// the builder type can be named, so other modules can build the struct through
+// it.
+//
+// The builder takes the visibility of the struct by default. A different
+// visibility can be given with #[builder(vis = "...")], it also applies to the
+// struct returned by `build_partial`.
+
+mod config {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[non_exhaustive]
+    pub struct Config {
+        name: String,
+        #[builder(each = "tag")]
+        tags: Vec<String>,
+        timeout: Option<u32>,
+    }
+
+    impl Config {
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        pub fn tags(&self) -> &[String] {
+            &self.tags
+        }
+    }
+
+    #[derive(Builder)]
+    #[builder(vis = "pub(super)")]
+    pub struct Secret {
+        value: String,
+    }
+}
+
+mod outer {
+    pub mod inner {
+        use derive_builder::Builder;
+
+        #[derive(Builder)]
+        #[builder(vis = "pub", build_fn(partial))]
+        pub(super) struct Draft {
+            pub title: String,
+        }
+    }
+
+    pub fn draft_builder() -> inner::DraftBuilder {
+        inner::Draft::builder()
+    }
+}
+
+fn main() {
+    let config = config::Config::builder()
+        .name("service".to_owned())
+        .tag("a".to_owned())
+        .timeout(10)
+        .build()
+        .unwrap();
+    assert_eq!(config.name(), "service");
+    assert_eq!(config.tags(), ["a"]);
+
+    let mut builder: config::SecretBuilder = config::Secret::builder();
+    builder.value("hunter2".to_owned());
+    assert!(builder.build().is_ok());
+
+    let mut builder = outer::draft_builder();
+    builder.title("notes".to_owned());
+    let partial: outer::inner::DraftPartial = builder.build_partial();
+    assert_eq!(partial.title.as_deref(), Some("notes"));
+}
@@ -0,0 +1,71 @@
+// The builder of a generic struct has the same generic parameters as the
+// struct, including lifetimes and where-clauses. Methods that need to clone or
+// print fields of a generic type only require that of the fields which
+// actually use a type parameter. Parameters only used by computed fields are
+// still declared on the builder.
+
+use derive_builder::Builder;
+use std::fmt::Display;
+
+#[derive(Builder)]
+#[builder(debug, build_fn(partial, or_default, wrappers(box)))]
+pub struct Labelled<'a, T, U = u8>
+where
+    U: Copy,
+{
+    label: &'a str,
+    value: T,
+    #[builder(each = "extra")]
+    extras: Vec<U>,
+    fallback: Option<T>,
+    #[builder(computed = "extras.len()")]
+    count: usize,
+}
+
+#[derive(Builder)]
+#[builder(build_fn(partial))]
+pub struct Comp<'a, T: Default> {
+    a: u8,
+    #[builder(computed = "T::default()")]
+    t: T,
+    #[builder(computed = "\"comp\"")]
+    name: &'a str,
+}
+
+fn describe<T: Display>(labelled: &Labelled<T>) -> String {
+    format!("{}={} ({})", labelled.label, labelled.value, labelled.count)
+}
+
+struct NotClone;
+
+fn main() {
+    let mut builder = Labelled::<i32>::builder();
+    builder.label("answer").value(42).extra(1).extra(2);
+    assert_eq!(
+        format!("{:?}", builder),
+        "LabelledBuilder { label: \"answer\", value: 42, extras: [1, 2], fallback: <unset> }",
+    );
+
+    let labelled = builder.build().unwrap();
+    assert_eq!(describe(&labelled), "answer=42 (2)");
+    assert_eq!(labelled.extras, vec![1u8, 2]);
+    assert!(labelled.fallback.is_none());
+
+    let boxed: Box<Labelled<i32>> = builder.build_boxed().unwrap();
+    assert_eq!(boxed.value, 42);
+
+    let partial: LabelledPartial<i32> = builder.clear_value().build_partial();
+    assert!(partial.value.is_none());
+    assert_eq!(builder.build_or_default().value, 0);
+
+    let comp = Comp::<String>::builder().a(1).build().unwrap();
+    assert_eq!((comp.a, comp.t.as_str(), comp.name), (1, "", "comp"));
+    let comp: Comp<u32> = Comp::builder().a(2).build().unwrap();
+    assert_eq!(comp.t, 0);
+    assert_eq!(Comp::<u32>::builder().a(3).build_partial().a, Some(3));
+
+    // Setters don't need anything from the type parameters
+    let mut builder = Labelled::<NotClone, u16>::builder();
+    builder.value(NotClone).extra(7u16);
+    assert!(builder.take_value().is_some());
+}
@@ -0,0 +1,35 @@
+// Fields named after keywords are written as raw identifiers. Their setters
+// keep the raw form, while generated names that merely contain the field name,
+// like `clear_type`, and field names in messages and Debug output don't.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(debug)]
+pub struct Item {
+    r#type: String,
+    #[builder(each = "match")]
+    r#matches: Vec<String>,
+    r#ref: Option<u32>,
+}
+
+fn main() {
+    let mut builder = Item::builder();
+    builder.r#match("a".to_owned()).r#ref(1);
+    assert_eq!(
+        format!("{:?}", builder),
+        "ItemBuilder { type: <unset>, matches: [\"a\"], ref: 1 }",
+    );
+
+    let err = builder.build().err().unwrap();
+    assert_eq!(err.to_string(), "missing field `type`");
+
+    builder.r#type("fruit".to_owned());
+    let item = builder.build().unwrap();
+    assert_eq!(item.r#type, "fruit");
+    assert_eq!(item.r#matches, vec!["a"]);
+
+    builder.clear_type().clear_matches();
+    assert!(builder.take_type().is_none());
+    assert!(builder.take_matches().is_empty());
+}
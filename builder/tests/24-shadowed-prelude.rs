@@ -0,0 +1,48 @@
+// Like test 09, but every prelude name the generated code could depend on is
+// shadowed, in the type namespace as well as the value namespace. The expanded
+// code has to refer to all of them through absolute paths.
+//
+// Fields declared with the fully qualified std::option::Option and
+// std::vec::Vec are still recognised as optional and repeated fields.
+
+#![allow(non_camel_case_types, non_snake_case, dead_code)]
+
+use derive_builder::Builder;
+
+type Option = ();
+type Result = ();
+type Box = ();
+type Vec = ();
+type String = ();
+type Clone = ();
+type Default = ();
+type From = ();
+type Into = ();
+
+fn Some() {}
+fn None() {}
+fn Ok() {}
+fn Err() {}
+
+#[derive(Builder)]
+#[builder(debug, build_fn(partial, or_default, wrappers(arc, rc, box)))]
+pub struct Command {
+    executable: std::string::String,
+    #[builder(each = "arg")]
+    args: std::vec::Vec<std::string::String>,
+    current_dir: std::option::Option<std::string::String>,
+    #[builder(setter(try_transform = |n: &str| n.parse::<u8>()))]
+    retries: u8,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    builder.executable("cargo".into()).arg("build".into()).retries("3");
+    let command = builder.build().unwrap();
+    assert_eq!(command.args, ["build"]);
+    assert_eq!(command.retries, 3);
+
+    builder.clear_current_dir();
+    assert!(builder.build_partial().current_dir.is_none());
+    assert!(builder.build_or_default().current_dir.is_none());
+}
@@ -0,0 +1,36 @@
+// The elements of a repeated field can themselves be optional, and a repeated
+// field can be combined with Option fields in the same struct.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<Option<String>>,
+    #[builder(each = "env")]
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg(Some("build".to_owned()))
+        .arg(None)
+        .env("RUST_LOG=debug".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec![Some("build".to_owned()), None]);
+    assert_eq!(command.env, vec!["RUST_LOG=debug"]);
+    assert!(command.current_dir.is_none());
+
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg(Some("test".to_owned()))
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(command.args, vec![Some("test".to_owned())]);
+    assert!(command.env.is_empty());
+}
@@ -0,0 +1,18 @@
+// Generated methods that end up with the same name are reported at the field
+// that causes the conflict, instead of as a duplicate definition pointing at
+// the derive.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "executable")]
+    args: Vec<String>,
+    #[builder(each = "build")]
+    steps: Vec<String>,
+    clear_env: bool,
+    env: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Fields with destructors work with #[builder(const)] too, even though a
+// const fn can't drop anything. A value set twice is replaced without being
+// dropped, and build_const moves every value out of the builder.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(const)]
+pub struct Device {
+    name: String,
+    aliases: Vec<String>,
+    firmware: Option<Vec<u8>>,
+}
+
+const DEVICE: Device = Device::builder()
+    .name(String::new())
+    .aliases(Vec::new())
+    .firmware(Vec::new())
+    .name(String::new())
+    .build_const();
+
+fn main() {
+    assert!(DEVICE.name.is_empty());
+    assert!(DEVICE.aliases.is_empty());
+    assert_eq!(DEVICE.firmware, Some(vec![]));
+
+    let device = Device::builder()
+        .name("uart0".to_owned())
+        .aliases(vec!["console".to_owned()])
+        .build_const();
+    assert_eq!(device.name, "uart0");
+    assert_eq!(device.aliases, ["console"]);
+    assert!(device.firmware.is_none());
+}
@@ -0,0 +1,12 @@
+// #[builder(serde)] derives serde::Deserialize for the builder, which is only
+// available with the `serde` feature of this crate.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(serde)]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {}
@@ -0,0 +1,103 @@
+pub(crate) struct EndpointBuilder {
+    host: std::option::Option<std::net::IpAddr>,
+    port: std::option::Option<u16>,
+    __setter_errors: std::vec::Vec<
+        (
+            &'static str,
+            std::sync::Arc<dyn std::error::Error + std::marker::Send + std::marker::Sync>,
+        ),
+    >,
+}
+impl Endpoint {
+    pub(crate) fn builder() -> EndpointBuilder {
+        EndpointBuilder {
+            host: std::option::Option::None,
+            port: std::option::Option::None,
+            __setter_errors: std::vec::Vec::new(),
+        }
+    }
+}
+impl EndpointBuilder {
+    pub fn host(&mut self, host: &str) -> &mut Self {
+        self.__setter_errors.retain(|(field, _)| *field != "host");
+        match (|host: &str| host.parse::<std::net::IpAddr>())(host) {
+            core::result::Result::Ok(host) => {
+                self.host = std::option::Option::Some(host);
+            }
+            core::result::Result::Err(err) => {
+                let err: std::boxed::Box<
+                    dyn std::error::Error + std::marker::Send + std::marker::Sync,
+                > = std::convert::Into::into(err);
+                self.__setter_errors.push(("host", std::sync::Arc::from(err)));
+            }
+        }
+        self
+    }
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.port = std::option::Option::Some(port);
+        self
+    }
+    pub fn clear_host(&mut self) -> &mut Self {
+        self.host = std::default::Default::default();
+        self.__setter_errors.retain(|(field, _)| *field != "host");
+        self
+    }
+    pub fn take_host(&mut self) -> std::option::Option<std::net::IpAddr> {
+        std::mem::take(&mut self.host)
+    }
+    pub fn clear_port(&mut self) -> &mut Self {
+        self.port = std::default::Default::default();
+        self
+    }
+    pub fn take_port(&mut self) -> std::option::Option<u16> {
+        std::mem::take(&mut self.port)
+    }
+    pub fn reset(&mut self) -> &mut Self {
+        *self = EndpointBuilder {
+            host: std::option::Option::None,
+            port: std::option::Option::None,
+            __setter_errors: std::vec::Vec::new(),
+        };
+        self
+    }
+    pub fn build(
+        &mut self,
+    ) -> core::result::Result<Endpoint, std::boxed::Box<dyn std::error::Error>> {
+        if let std::option::Option::Some((field, err)) = self.__setter_errors.first() {
+            #[derive(Debug)]
+            struct SetterError(
+                &'static str,
+                std::sync::Arc<
+                    dyn std::error::Error + std::marker::Send + std::marker::Sync,
+                >,
+            );
+            impl std::fmt::Display for SetterError {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::write!(f, "invalid value for `{}`: {}", self.0, self.1)
+                }
+            }
+            impl std::error::Error for SetterError {
+                fn source(
+                    &self,
+                ) -> std::option::Option<&(dyn std::error::Error + 'static)> {
+                    std::option::Option::Some(&*self.1)
+                }
+            }
+            return core::result::Result::Err(
+                std::boxed::Box::new(SetterError(field, std::sync::Arc::clone(err))),
+            );
+        }
+        let std::option::Option::Some(host) = std::clone::Clone::clone(&self.host) else {
+            return core::result::Result::Err(
+                std::convert::From::from("missing field `host`"),
+            );
+        };
+        let std::option::Option::Some(port) = std::clone::Clone::clone(&self.port) else {
+            return core::result::Result::Err(
+                std::convert::From::from("missing field `port`"),
+            );
+        };
+        let addr: std::net::SocketAddr = std::net::SocketAddr::new(host, port);
+        core::result::Result::Ok(Endpoint { host, port, addr })
+    }
+}
@@ -0,0 +1,9 @@
+#[derive(Builder)]
+#[builder(vis = "pub(crate)")]
+pub struct Endpoint {
+    #[builder(setter(try_transform = |host: &str| host.parse::<std::net::IpAddr>()))]
+    host: std::net::IpAddr,
+    port: u16,
+    #[builder(computed = "std::net::SocketAddr::new(host, port)")]
+    addr: std::net::SocketAddr,
+}
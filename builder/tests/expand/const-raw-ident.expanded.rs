@@ -0,0 +1,66 @@
+pub struct PinBuilder {
+    r#type: std::option::Option<u8>,
+    pull_up: Option<bool>,
+}
+impl Pin {
+    pub const fn builder() -> PinBuilder {
+        PinBuilder {
+            r#type: std::option::Option::None,
+            pull_up: std::option::Option::None,
+        }
+    }
+}
+impl PinBuilder {
+    pub const fn r#type(mut self, r#type: u8) -> Self {
+        let _ = core::mem::ManuallyDrop::new(
+            core::mem::replace(&mut self.r#type, std::option::Option::Some(r#type)),
+        );
+        self
+    }
+    pub const fn pull_up(mut self, pull_up: bool) -> Self {
+        let _ = core::mem::ManuallyDrop::new(
+            core::mem::replace(&mut self.pull_up, std::option::Option::Some(pull_up)),
+        );
+        self
+    }
+    pub fn clear_type(&mut self) -> &mut Self {
+        self.r#type = std::default::Default::default();
+        self
+    }
+    pub fn take_type(&mut self) -> std::option::Option<u8> {
+        std::mem::take(&mut self.r#type)
+    }
+    pub fn clear_pull_up(&mut self) -> &mut Self {
+        self.pull_up = std::default::Default::default();
+        self
+    }
+    pub fn take_pull_up(&mut self) -> Option<bool> {
+        std::mem::take(&mut self.pull_up)
+    }
+    pub fn reset(&mut self) -> &mut Self {
+        *self = PinBuilder {
+            r#type: std::option::Option::None,
+            pull_up: std::option::Option::None,
+        };
+        self
+    }
+    pub fn build(
+        &mut self,
+    ) -> core::result::Result<Pin, std::boxed::Box<dyn std::error::Error>> {
+        let std::option::Option::Some(r#type) = std::clone::Clone::clone(&self.r#type)
+        else {
+            return core::result::Result::Err(
+                std::convert::From::from("missing field `type`"),
+            );
+        };
+        let pull_up = std::clone::Clone::clone(&self.pull_up);
+        core::result::Result::Ok(Pin { r#type, pull_up })
+    }
+    pub const fn build_const(mut self) -> Pin {
+        let r#type = self.r#type.take();
+        let pull_up = self.pull_up.take();
+        core::mem::forget(self);
+        let r#type = r#type.expect("missing field `type`");
+        Pin { r#type, pull_up }
+    }
+}
@@ -0,0 +1,6 @@
+#[derive(Builder)]
+#[builder(const)]
+pub struct Pin {
+    r#type: u8,
+    pull_up: Option<bool>,
+}
@@ -0,0 +1,149 @@
+pub struct LabelledBuilder<'a, T>
+where
+    T: Copy,
+{
+    label: std::option::Option<&'a str>,
+    value: std::option::Option<T>,
+    secret: Option<String>,
+}
+pub struct LabelledPartial<'a, T>
+where
+    T: Copy,
+{
+    label: std::option::Option<&'a str>,
+    value: std::option::Option<T>,
+    secret: std::option::Option<String>,
+}
+impl<'a, T> Labelled<'a, T>
+where
+    T: Copy,
+{
+    pub fn builder() -> LabelledBuilder<'a, T> {
+        LabelledBuilder {
+            label: std::option::Option::None,
+            value: std::option::Option::None,
+            secret: std::option::Option::None,
+        }
+    }
+}
+impl<'a, T> LabelledBuilder<'a, T>
+where
+    T: Copy,
+{
+    pub fn label(&mut self, label: &'a str) -> &mut Self {
+        self.label = std::option::Option::Some(label);
+        self
+    }
+    pub fn value(&mut self, value: T) -> &mut Self {
+        self.value = std::option::Option::Some(value);
+        self
+    }
+    pub fn secret(&mut self, secret: String) -> &mut Self {
+        self.secret = std::option::Option::Some(secret);
+        self
+    }
+    pub fn clear_label(&mut self) -> &mut Self {
+        self.label = std::default::Default::default();
+        self
+    }
+    pub fn take_label(&mut self) -> std::option::Option<&'a str> {
+        std::mem::take(&mut self.label)
+    }
+    pub fn clear_value(&mut self) -> &mut Self {
+        self.value = std::default::Default::default();
+        self
+    }
+    pub fn take_value(&mut self) -> std::option::Option<T> {
+        std::mem::take(&mut self.value)
+    }
+    pub fn clear_secret(&mut self) -> &mut Self {
+        self.secret = std::default::Default::default();
+        self
+    }
+    pub fn take_secret(&mut self) -> Option<String> {
+        std::mem::take(&mut self.secret)
+    }
+    pub fn reset(&mut self) -> &mut Self {
+        *self = LabelledBuilder {
+            label: std::option::Option::None,
+            value: std::option::Option::None,
+            secret: std::option::Option::None,
+        };
+        self
+    }
+    pub fn build(
+        &mut self,
+    ) -> core::result::Result<Labelled<'a, T>, std::boxed::Box<dyn std::error::Error>>
+    where
+        T: std::clone::Clone,
+    {
+        let std::option::Option::Some(label) = std::clone::Clone::clone(&self.label)
+        else {
+            return core::result::Result::Err(
+                std::convert::From::from("missing field `label`"),
+            );
+        };
+        let std::option::Option::Some(value) = std::clone::Clone::clone(&self.value)
+        else {
+            return core::result::Result::Err(
+                std::convert::From::from("missing field `value`"),
+            );
+        };
+        let secret = std::clone::Clone::clone(&self.secret);
+        core::result::Result::Ok(Labelled { label, value, secret })
+    }
+    pub fn build_arc(
+        &mut self,
+    ) -> core::result::Result<
+        std::sync::Arc<Labelled<'a, T>>,
+        std::boxed::Box<dyn std::error::Error>,
+    >
+    where
+        T: std::clone::Clone,
+    {
+        self.build().map(std::sync::Arc::new)
+    }
+    pub fn build_partial(&self) -> LabelledPartial<'a, T>
+    where
+        T: std::clone::Clone,
+    {
+        LabelledPartial {
+            label: std::clone::Clone::clone(&self.label),
+            value: std::clone::Clone::clone(&self.value),
+            secret: std::clone::Clone::clone(&self.secret),
+        }
+    }
+    pub fn build_or_default(&mut self) -> Labelled<'a, T>
+    where
+        T: std::clone::Clone + std::default::Default,
+    {
+        let label = std::clone::Clone::clone(&self.label).unwrap_or_default();
+        let value = std::clone::Clone::clone(&self.value).unwrap_or_default();
+        let secret = std::clone::Clone::clone(&self.secret);
+        Labelled { label, value, secret }
+    }
+}
+impl<'a, T> std::fmt::Debug for LabelledBuilder<'a, T>
+where
+    T: Copy,
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("LabelledBuilder");
+        match &self.label {
+            std::option::Option::Some(label) => debug.field("label", label),
+            std::option::Option::None => debug.field("label", &format_args!("<unset>")),
+        };
+        match &self.value {
+            std::option::Option::Some(value) => debug.field("value", value),
+            std::option::Option::None => debug.field("value", &format_args!("<unset>")),
+        };
+        match &self.secret {
+            std::option::Option::Some(_) => {
+                debug.field("secret", &format_args!("<redacted>"))
+            }
+            std::option::Option::None => debug.field("secret", &format_args!("<unset>")),
+        };
+        debug.finish()
+    }
+}
@@ -0,0 +1,11 @@
+#[derive(Builder)]
+#[builder(debug, build_fn(partial, or_default, wrappers(arc)))]
+pub struct Labelled<'a, T>
+where
+    T: Copy,
+{
+    label: &'a str,
+    value: T,
+    #[builder(redact)]
+    secret: Option<String>,
+}
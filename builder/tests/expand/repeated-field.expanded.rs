@@ -0,0 +1,91 @@
+pub struct CommandBuilder {
+    executable: std::option::Option<String>,
+    args: Vec<String>,
+    env: Vec<String>,
+    current_dir: Option<String>,
+}
+impl Command {
+    pub fn builder() -> CommandBuilder {
+        CommandBuilder {
+            executable: std::option::Option::None,
+            args: std::vec::Vec::new(),
+            env: std::vec::Vec::new(),
+            current_dir: std::option::Option::None,
+        }
+    }
+}
+impl CommandBuilder {
+    pub fn executable(&mut self, executable: String) -> &mut Self {
+        self.executable = std::option::Option::Some(executable);
+        self
+    }
+    pub fn arg(&mut self, arg: String) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+    pub fn env(&mut self, env: String) -> &mut Self {
+        self.env.push(env);
+        self
+    }
+    pub fn current_dir(&mut self, current_dir: String) -> &mut Self {
+        self.current_dir = std::option::Option::Some(current_dir);
+        self
+    }
+    pub fn clear_executable(&mut self) -> &mut Self {
+        self.executable = std::default::Default::default();
+        self
+    }
+    pub fn take_executable(&mut self) -> std::option::Option<String> {
+        std::mem::take(&mut self.executable)
+    }
+    pub fn clear_args(&mut self) -> &mut Self {
+        self.args = std::default::Default::default();
+        self
+    }
+    pub fn take_args(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.args)
+    }
+    pub fn clear_env(&mut self) -> &mut Self {
+        self.env = std::default::Default::default();
+        self
+    }
+    pub fn take_env(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.env)
+    }
+    pub fn clear_current_dir(&mut self) -> &mut Self {
+        self.current_dir = std::default::Default::default();
+        self
+    }
+    pub fn take_current_dir(&mut self) -> Option<String> {
+        std::mem::take(&mut self.current_dir)
+    }
+    pub fn reset(&mut self) -> &mut Self {
+        *self = CommandBuilder {
+            executable: std::option::Option::None,
+            args: std::vec::Vec::new(),
+            env: std::vec::Vec::new(),
+            current_dir: std::option::Option::None,
+        };
+        self
+    }
+    pub fn build(
+        &mut self,
+    ) -> core::result::Result<Command, std::boxed::Box<dyn std::error::Error>> {
+        let std::option::Option::Some(executable) = std::clone::Clone::clone(
+            &self.executable,
+        ) else {
+            return core::result::Result::Err(
+                std::convert::From::from("missing field `executable`"),
+            );
+        };
+        let args = std::clone::Clone::clone(&self.args);
+        let env = std::clone::Clone::clone(&self.env);
+        let current_dir = std::clone::Clone::clone(&self.current_dir);
+        core::result::Result::Ok(Command {
+            executable,
+            args,
+            env,
+            current_dir,
+        })
+    }
+}
@@ -10,4 +10,25 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-builder-debug.rs");
+    t.pass("tests/11-partial-build.rs");
+    t.pass("tests/12-reset-builder.rs");
+    t.pass("tests/13-computed-field.rs");
+    t.compile_fail("tests/14-computed-cycle.rs");
+    t.pass("tests/15-build-wrappers.rs");
+    t.compile_fail("tests/16-all-attribute-errors.rs");
+    t.pass("tests/17-setter-transform.rs");
+    t.pass("tests/18-const-builder.rs");
+    t.compile_fail("tests/19-const-missing-field.rs");
+    #[cfg(feature = "serde")]
+    t.pass("tests/20-serde.rs");
+    t.pass("tests/21-builder-visibility.rs");
+    t.pass("tests/22-generic-struct.rs");
+    t.pass("tests/23-raw-identifiers.rs");
+    t.pass("tests/24-shadowed-prelude.rs");
+    t.pass("tests/25-each-and-option.rs");
+    t.compile_fail("tests/26-conflicting-setters.rs");
+    t.pass("tests/27-const-owned-fields.rs");
+    #[cfg(not(feature = "serde"))]
+    t.compile_fail("tests/28-serde-without-feature.rs");
 }
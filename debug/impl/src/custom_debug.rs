@@ -0,0 +1,1496 @@
+use quote::{format_ident, quote, quote_spanned};
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned};
+
+use crate::derive_trait::DeriveTrait;
+use crate::generic_bounds::BoundInference;
+use crate::rename_rule::{RenameRule, RENAME_RULES};
+
+#[allow(dead_code)]
+pub struct CustomDebugInput<D> {
+    attr: CustomDebugAttribute<D>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: CustomDebugData<D>,
+}
+
+#[allow(dead_code)]
+enum CustomDebugData<D> {
+    Struct {
+        struct_token: syn::Token![struct],
+        fields: CustomDebugFields<D>,
+        semi_token: Option<syn::Token![;]>,
+        format: Option<FormatString>,
+    },
+    Enum {
+        enum_token: syn::Token![enum],
+        brace_token: syn::token::Brace,
+        variants: syn::punctuated::Punctuated<CustomDebugVariant<D>, syn::Token![,]>,
+    },
+}
+
+impl<D: DeriveTrait> syn::parse::Parse for CustomDebugInput<D> {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attr: CustomDebugAttribute<D> = input.parse()?;
+        attr.check_context(AttributeContext::Container)?;
+        let vis: syn::Visibility = input.parse()?;
+        let lookahead = input.lookahead1();
+        if lookahead.peek(syn::Token![struct]) {
+            let struct_token = input.parse()?;
+            let ident = input.parse()?;
+            let mut generics: syn::Generics = input.parse()?;
+            // The where clause goes before the fields of braced and unit structs but after the
+            // fields of tuple structs, ie. `struct Wrapper<T>(T) where T: Copy;`
+            if !input.peek(syn::token::Paren) {
+                generics.where_clause = input.parse()?;
+            }
+            let fields: CustomDebugFields<D> = input.parse()?;
+            // Only braced structs go without a trailing semicolon, ie. `struct Id(u64);`
+            let semi_token = match fields {
+                CustomDebugFields::Named { .. } => None,
+                CustomDebugFields::Unnamed { .. } => {
+                    generics.where_clause = input.parse()?;
+                    Some(input.parse()?)
+                }
+                CustomDebugFields::Unit => Some(input.parse()?),
+            };
+            let format = FormatString::for_fields(&attr, &ident, &fields)?;
+            // Skipped fields, ie. a `PhantomData`, can sit next to the one that's printed
+            if let Some(transparent) = &attr.transparent {
+                if fields.shown().iter().filter(|shown| **shown).count() != 1 {
+                    return Err(syn::Error::new(
+                        transparent.span(),
+                        "`transparent` needs exactly one field that isn't skipped",
+                    ));
+                }
+            }
+            Ok(CustomDebugInput {
+                attr,
+                vis,
+                ident,
+                generics,
+                data: CustomDebugData::Struct {
+                    struct_token,
+                    fields,
+                    semi_token,
+                    format,
+                },
+            })
+        } else if lookahead.peek(syn::Token![enum]) {
+            if attr.debug.is_some() {
+                return Err(syn::Error::new(
+                    attr.span.expect("Some(span)"),
+                    format!(
+                        "direct {} attribute only accepted on structs, variants and fields",
+                        D::ATTRIBUTE
+                    ),
+                ));
+            }
+            if let Some(transparent) = &attr.transparent {
+                return Err(syn::Error::new(
+                    transparent.span(),
+                    "`transparent` only accepted on structs",
+                ));
+            }
+            let enum_token = input.parse()?;
+            let ident = input.parse()?;
+            let mut generics: syn::Generics = input.parse()?;
+            generics.where_clause = input.parse()?;
+            let content;
+            let brace_token = syn::braced!(content in input);
+            Ok(CustomDebugInput {
+                attr,
+                vis,
+                ident,
+                generics,
+                data: CustomDebugData::Enum {
+                    enum_token,
+                    brace_token,
+                    variants: content
+                        .parse_terminated(CustomDebugVariant::parse, syn::Token![,])?,
+                },
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct CustomDebugVariant<D> {
+    attr: CustomDebugAttribute<D>,
+    ident: syn::Ident,
+    fields: CustomDebugFields<D>,
+    format: Option<FormatString>,
+    discriminant: Option<(syn::Token![=], syn::Expr)>,
+}
+
+impl<D: DeriveTrait> syn::parse::Parse for CustomDebugVariant<D> {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attr: CustomDebugAttribute<D> = input.parse()?;
+        attr.check_context(AttributeContext::Variant)?;
+        let ident = input.parse()?;
+        let fields = input.parse()?;
+        let format = FormatString::for_fields(&attr, &ident, &fields)?;
+        Ok(CustomDebugVariant {
+            attr,
+            ident,
+            fields,
+            format,
+            discriminant: if input.peek(syn::Token![=]) {
+                Some((input.parse()?, input.parse()?))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl<D: DeriveTrait> CustomDebugVariant<D> {
+    // The name printed for the variant, `rename_all` being the enum's rule
+    fn debug_name(&self, rename_all: Option<RenameRule>) -> String {
+        let ident = self.ident.unraw().to_string();
+        if let Some(rename) = &self.attr.rename {
+            rename.value()
+        } else if let Some(rule) = rename_all {
+            rule.apply_to_variant(&ident)
+        } else {
+            ident
+        }
+    }
+}
+
+#[allow(dead_code)]
+enum CustomDebugFields<D> {
+    Named {
+        brace_token: syn::token::Brace,
+        fields: syn::punctuated::Punctuated<CustomDebugField<D>, syn::Token![,]>,
+    },
+    Unnamed {
+        paren_token: syn::token::Paren,
+        fields: syn::punctuated::Punctuated<CustomDebugField<D>, syn::Token![,]>,
+    },
+    Unit,
+}
+
+impl<D: DeriveTrait> syn::parse::Parse for CustomDebugFields<D> {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        if input.peek(syn::token::Brace) {
+            Ok(CustomDebugFields::Named {
+                brace_token: syn::braced!(content in input),
+                fields: content.parse_terminated(CustomDebugField::parse_named, syn::Token![,])?,
+            })
+        } else if input.peek(syn::token::Paren) {
+            Ok(CustomDebugFields::Unnamed {
+                paren_token: syn::parenthesized!(content in input),
+                fields: content
+                    .parse_terminated(CustomDebugField::parse_unnamed, syn::Token![,])?,
+            })
+        } else {
+            Ok(CustomDebugFields::Unit)
+        }
+    }
+}
+
+impl<D: DeriveTrait> CustomDebugFields<D> {
+    fn iter(&self) -> impl Iterator<Item = &CustomDebugField<D>> {
+        let fields = match self {
+            CustomDebugFields::Named { fields, .. } | CustomDebugFields::Unnamed { fields, .. } => {
+                Some(fields.iter())
+            }
+            CustomDebugFields::Unit => None,
+        };
+        fields.into_iter().flatten()
+    }
+
+    // Fields are bound by position, ie. `__self_0`, so they can't clash with the formatter
+    fn bindings(&self) -> Vec<syn::Ident> {
+        (0..self.iter().count())
+            .map(|i| format_ident!("__self_{}", i))
+            .collect()
+    }
+
+    // Which fields `debug_body` prints, by position
+    fn shown(&self) -> Vec<bool> {
+        self.iter().map(|f| f.attr.skip.is_none()).collect()
+    }
+
+    // The pattern matching `path` that binds the fields marked in `bound` and ignores the rest,
+    // eg. `Self::Variant { a: __self_0, b: _ }`
+    fn pattern(&self, path: proc_macro2::TokenStream, bound: &[bool]) -> proc_macro2::TokenStream {
+        let bindings = self
+            .bindings()
+            .into_iter()
+            .zip(bound)
+            .map(
+                |(binding, bound)| {
+                    if *bound {
+                        quote!(#binding)
+                    } else {
+                        quote!(_)
+                    }
+                },
+            );
+        match self {
+            CustomDebugFields::Named { fields, .. } => {
+                let names = fields.iter().map(|f| &f.field.ident);
+                quote! { #path { #(#names: #bindings),* } }
+            }
+            CustomDebugFields::Unnamed { .. } => quote! { #path(#(#bindings),*) },
+            CustomDebugFields::Unit => path,
+        }
+    }
+
+    // Formats the bound fields under `name`, the way `#[derive(Debug)]` would. Skipping a field
+    // ends the output with `..` so it doesn't look like the whole value, fields left out at
+    // runtime by `skip_if` or `skip_defaults` are just empty and don't
+    fn debug_body(
+        &self,
+        name: &str,
+        rename_all: Option<RenameRule>,
+        skip_defaults: bool,
+    ) -> proc_macro2::TokenStream {
+        let finish = if self.shown().contains(&false) {
+            quote!(finish_non_exhaustive)
+        } else {
+            quote!(finish)
+        };
+        let builder = match self {
+            CustomDebugFields::Named { .. } => quote!(f.debug_struct(#name)),
+            CustomDebugFields::Unnamed { .. } => quote!(f.debug_tuple(#name)),
+            CustomDebugFields::Unit => {
+                return quote! {
+                    f.write_str(#name)
+                }
+            }
+        };
+        let fields = self.field_statements(rename_all, skip_defaults, false);
+        quote! {{
+            let debug = &mut #builder;
+            #(#fields)*
+            debug.#finish()
+        }}
+    }
+
+    // The statements adding each shown field to `debug`, the struct or tuple builder, or when
+    // `visit` is set passing it to `visitor`, a `&mut dyn FieldVisitor`
+    fn field_statements(
+        &self,
+        rename_all: Option<RenameRule>,
+        skip_defaults: bool,
+        visit: bool,
+    ) -> Vec<proc_macro2::TokenStream> {
+        self.iter()
+            .zip(self.bindings())
+            .enumerate()
+            .filter(|(_, (f, _))| f.attr.skip.is_none())
+            .map(|(i, (f, binding))| {
+                let value = f.debug_value(&binding);
+                let name = match self {
+                    CustomDebugFields::Named { .. } => f.debug_name(rename_all),
+                    _ => i.to_string(),
+                };
+                // Spanned on the field's type, one that can't be flattened is reported there
+                let flattened = syn::Ident::new(&binding.to_string(), f.field.ty.span());
+                let field = match self {
+                    _ if visit && f.attr.flatten.is_some() => quote_spanned! { f.field.ty.span() =>
+                        ::derive_debug::FieldVisit::visit_fields(#flattened, visitor);
+                    },
+                    _ if visit => quote! {
+                        ::derive_debug::FieldVisitor::visit_field(visitor, #name, #value);
+                    },
+                    // The flattened struct adds its own fields, see `debug_fields_impl`
+                    _ if f.attr.flatten.is_some() => quote_spanned! { f.field.ty.span() =>
+                        ::derive_debug::DebugFields::debug_fields(#flattened, debug);
+                    },
+                    CustomDebugFields::Named { .. } => quote! { debug.field(#name, #value); },
+                    _ => quote! { debug.field(#value); },
+                };
+                let ty = &f.field.ty;
+                if let Some(skip_if) = &f.attr.skip_if {
+                    quote! {
+                        if !(#skip_if)(#binding) {
+                            #field
+                        }
+                    }
+                } else if skip_defaults {
+                    quote! {
+                        if *#binding != <#ty as std::default::Default>::default() {
+                            #field
+                        }
+                    }
+                } else {
+                    field
+                }
+            })
+            .collect()
+    }
+}
+
+// A struct or variant level `#[debug("...")]` printing the whole value. Field names in it are
+// rewritten to the bindings of those fields, ie. `{x:.2}` to `{__self_0:.2}`, so only the fields
+// actually used need to be bound
+struct FormatString {
+    lit: syn::LitStr,
+    // The formatting traits the string uses each field through, by position
+    traits: Vec<Vec<syn::Path>>,
+    // Whether each field is used as a width or precision, ie. `{x:>width$}`. Those are passed
+    // as named arguments, `__count_N`, as the bindings are references and counts `usize`s
+    counts: Vec<bool>,
+}
+
+impl FormatString {
+    // The format string of a struct or variant, if it has one. Derives without a field by field
+    // fallback need one unless there are no fields
+    fn for_fields<D: DeriveTrait>(
+        attr: &CustomDebugAttribute<D>,
+        ident: &syn::Ident,
+        fields: &CustomDebugFields<D>,
+    ) -> syn::Result<Option<Self>> {
+        match &attr.debug {
+            Some(lit) => FormatString::new(lit, fields).map(Some),
+            None if !D::FIELDS_FALLBACK && !matches!(fields, CustomDebugFields::Unit) => {
+                Err(syn::Error::new(
+                    ident.span(),
+                    format!("expected a `#[{}(\"...\")]` format string", D::ATTRIBUTE),
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn new<D: DeriveTrait>(lit: &syn::LitStr, fields: &CustomDebugFields<D>) -> syn::Result<Self> {
+        let value = lit.value();
+        let mut traits = vec![vec![]; fields.iter().count()];
+        let mut counts = vec![false; fields.iter().count()];
+        let mut rewritten = String::with_capacity(value.len());
+        let mut copied = 0;
+        let position = |arg: &str| {
+            let position = match fields {
+                CustomDebugFields::Named { fields, .. } => fields.iter().position(|f| {
+                    f.field
+                        .ident
+                        .as_ref()
+                        .is_some_and(|ident| ident.unraw() == arg)
+                }),
+                CustomDebugFields::Unnamed { fields, .. } => {
+                    arg.parse().ok().filter(|index| *index < fields.len())
+                }
+                CustomDebugFields::Unit => None,
+            };
+            position
+                .ok_or_else(|| syn::Error::new(lit.span(), format!("no field `{arg}` to format")))
+        };
+        for placeholder in placeholders(&value) {
+            let arg = &value[placeholder.arg.clone()];
+            // Implicit positional arguments are left for the compiler to complain about
+            if !arg.is_empty() {
+                let position = position(arg)?;
+                traits[position].push(spec_trait(&placeholder.spec));
+                rewritten.push_str(&value[copied..placeholder.arg.start]);
+                rewritten.push_str(&format!("__self_{}", position));
+                copied = placeholder.arg.end;
+            }
+            for count in placeholder.counts {
+                let position = position(&value[count.clone()])?;
+                counts[position] = true;
+                rewritten.push_str(&value[copied..count.start]);
+                rewritten.push_str(&format!("__count_{}", position));
+                copied = count.end;
+            }
+        }
+        rewritten.push_str(&value[copied..]);
+        Ok(FormatString {
+            lit: syn::LitStr::new(&rewritten, lit.span()),
+            traits,
+            counts,
+        })
+    }
+
+    // Which fields the string refers to, by position
+    fn used(&self) -> Vec<bool> {
+        self.traits
+            .iter()
+            .zip(&self.counts)
+            .map(|(traits, count)| !traits.is_empty() || *count)
+            .collect()
+    }
+
+    fn body(&self) -> proc_macro2::TokenStream {
+        let lit = &self.lit;
+        let counts = self
+            .counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count)
+            .map(|(i, _)| {
+                let name = format_ident!("__count_{}", i);
+                let binding = format_ident!("__self_{}", i);
+                quote! { #name = *#binding }
+            });
+        quote! {
+            f.write_fmt(format_args!(#lit #(, #counts)*))
+        }
+    }
+}
+
+// A `{arg:spec}` in a format string, `arg` being the byte range of the argument and `counts`
+// those of the arguments the spec takes its width or precision from, ie. `w` in `{:>w$}`
+struct Placeholder {
+    arg: std::ops::Range<usize>,
+    spec: String,
+    counts: Vec<std::ops::Range<usize>>,
+}
+
+fn placeholders(value: &str) -> Vec<Placeholder> {
+    let mut placeholders = vec![];
+    let mut chars = value.char_indices().peekable();
+    while let Some((_, ch)) = chars.next() {
+        match (ch, chars.peek()) {
+            ('{', Some((_, '{'))) | ('}', Some((_, '}'))) => {
+                chars.next();
+            }
+            ('{', _) => {
+                let start = chars.peek().map_or(value.len(), |(i, _)| *i);
+                let mut end = start;
+                while let Some((i, ch)) = chars.next_if(|(_, ch)| *ch != '}' && *ch != ':') {
+                    end = i + ch.len_utf8();
+                }
+                let mut spec = String::new();
+                let mut counts = vec![];
+                if chars.next_if(|(_, ch)| *ch == ':').is_some() {
+                    let spec_start = chars.peek().map_or(value.len(), |(i, _)| *i);
+                    while let Some((_, ch)) = chars.next_if(|(_, ch)| *ch != '}') {
+                        spec.push(ch);
+                    }
+                    counts = spec_counts(&spec)
+                        .into_iter()
+                        .map(|count| spec_start + count.start..spec_start + count.end)
+                        .collect();
+                }
+                // The closing brace
+                chars.next();
+                placeholders.push(Placeholder {
+                    arg: start..end,
+                    spec,
+                    counts,
+                });
+            }
+            _ => {}
+        }
+    }
+    placeholders
+}
+
+// The arguments before the `$`s of a spec, ie. `w` and `p` in `>w$.p$`. A fill character of
+// `$` has nothing before it, and a `0` flag in front of the width isn't part of it
+fn spec_counts(spec: &str) -> Vec<std::ops::Range<usize>> {
+    let mut counts = vec![];
+    for (dollar, _) in spec.match_indices('$') {
+        let start = spec[..dollar]
+            .rfind(|ch: char| !ch.is_alphanumeric() && ch != '_')
+            .map_or(0, |i| i + 1);
+        let mut count = &spec[start..dollar];
+        if count.starts_with('0') && count.len() > 1 {
+            count = &count[1..];
+        }
+        if !count.is_empty() {
+            counts.push(dollar - count.len()..dollar);
+        }
+    }
+    counts
+}
+
+// The formatting trait a format spec asks for, ie. `LowerHex` for `{:08x}`
+fn spec_trait(spec: &str) -> syn::Path {
+    match spec.chars().last() {
+        Some('?') => parse_quote!(std::fmt::Debug),
+        Some('x') => parse_quote!(std::fmt::LowerHex),
+        Some('X') => parse_quote!(std::fmt::UpperHex),
+        Some('o') => parse_quote!(std::fmt::Octal),
+        Some('b') => parse_quote!(std::fmt::Binary),
+        Some('e') => parse_quote!(std::fmt::LowerExp),
+        Some('E') => parse_quote!(std::fmt::UpperExp),
+        _ => parse_quote!(std::fmt::Display),
+    }
+}
+
+struct CustomDebugAttribute<D> {
+    debug: Option<syn::LitStr>,
+    bound: Option<syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>>,
+    // The `skip` key itself, kept around for its span
+    skip: Option<syn::Ident>,
+    rename: Option<syn::LitStr>,
+    rename_all: Option<(syn::LitStr, RenameRule)>,
+    redact: Option<Redact>,
+    // A `fn(&T, &mut fmt::Formatter) -> fmt::Result` formatting the field instead of `Debug`
+    with: Option<syn::ExprPath>,
+    // Iterable fields only print this many elements
+    limit: Option<syn::LitInt>,
+    // String-like fields only print this many characters
+    max_len: Option<syn::LitInt>,
+    // One of `hex`, `bin`, `octal` or `bytes`
+    preset: Option<syn::Ident>,
+    // A `fn(&T) -> bool` leaving the field out of the output when it returns true
+    skip_if: Option<syn::ExprPath>,
+    // The `skip_defaults` key itself, kept around for its span
+    skip_defaults: Option<syn::Ident>,
+    // The `flatten` key itself, the field's own fields are printed in its place
+    flatten: Option<syn::Ident>,
+    // The `transparent` key itself, the struct is printed as its only field
+    transparent: Option<syn::Ident>,
+    // The `visit` key itself, asking for a `FieldVisit` impl as well
+    visit: Option<syn::Ident>,
+    span: Option<proc_macro2::Span>,
+    derive: std::marker::PhantomData<D>,
+}
+
+// `#[debug(redact)]`, `#[debug(redact = "***")]` or `#[debug(redact(partial = 4))]`
+struct Redact {
+    // The `redact` key itself, kept around for its span
+    key: syn::Ident,
+    placeholder: Option<syn::LitStr>,
+    // How many trailing characters of the `Display` output are shown after the placeholder
+    partial: Option<syn::LitInt>,
+}
+
+// Numeric presentations, all but `bytes` format integers or the integers in a `Vec`, array or
+// slice element-wise
+const PRESETS: &[&str] = &["hex", "bin", "octal", "bytes"];
+
+// The trait a preset formats integers through, `bytes` formats the whole value at once
+fn preset_trait(preset: &syn::Ident) -> Option<syn::Path> {
+    if preset == "hex" {
+        Some(parse_quote!(std::fmt::LowerHex))
+    } else if preset == "bin" {
+        Some(parse_quote!(std::fmt::Binary))
+    } else if preset == "octal" {
+        Some(parse_quote!(std::fmt::Octal))
+    } else {
+        None
+    }
+}
+
+// The element type of the sequences presets format element-wise
+fn element_type(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Array(array) => Some(&array.elem),
+        syn::Type::Slice(slice) => Some(&slice.elem),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            if segment.ident != "Vec" && segment.ident != "VecDeque" {
+                return None;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return None;
+            };
+            match args.args.first()? {
+                syn::GenericArgument::Type(elem) => Some(elem),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Where a `#[debug]` attribute was found, not every key makes sense everywhere
+#[derive(Clone, Copy, PartialEq)]
+enum AttributeContext {
+    Container,
+    Variant,
+    NamedField,
+    UnnamedField,
+}
+
+impl<D: DeriveTrait> syn::parse::Parse for CustomDebugAttribute<D> {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs: Vec<syn::Attribute> = input.call(syn::Attribute::parse_outer)?;
+        let debug_attrs: Vec<&syn::Attribute> = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(D::ATTRIBUTE))
+            .collect();
+        let mut debug_attribute = CustomDebugAttribute {
+            debug: None,
+            bound: None,
+            skip: None,
+            rename: None,
+            rename_all: None,
+            redact: None,
+            with: None,
+            limit: None,
+            max_len: None,
+            preset: None,
+            skip_if: None,
+            skip_defaults: None,
+            flatten: None,
+            transparent: None,
+            visit: None,
+            span: debug_attrs.first().map(|attr| attr.span()),
+            derive: std::marker::PhantomData,
+        };
+        for attr in debug_attrs {
+            match &attr.meta {
+                syn::Meta::Path(_) => {
+                    return Err(syn::Error::new(
+                        attr.span(),
+                        format!("expected `{0} = \" ... \" or {0}(\" ... \")`", D::ATTRIBUTE),
+                    ))
+                }
+                // `debug("...")` is the same as `debug = "..."`
+                syn::Meta::List(ref meta_list)
+                    if syn::parse2::<syn::LitStr>(meta_list.tokens.clone()).is_ok() =>
+                {
+                    debug_attribute.debug = Some(meta_list.parse_args()?);
+                }
+                syn::Meta::List(ref meta_list) => {
+                    meta_list.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("bound") {
+                            let lit_str: syn::LitStr = meta.value()?.parse()?;
+                            debug_attribute.bound = Some(
+                                lit_str.parse_with(syn::punctuated::Punctuated::parse_terminated)?,
+                            );
+                            Ok(())
+                        } else if meta.path.is_ident("skip") {
+                            debug_attribute.skip = meta.path.get_ident().cloned();
+                            Ok(())
+                        } else if meta.path.is_ident("rename") {
+                            debug_attribute.rename = Some(meta.value()?.parse()?);
+                            Ok(())
+                        } else if meta.path.is_ident("rename_all") {
+                            let lit_str: syn::LitStr = meta.value()?.parse()?;
+                            let Some(rule) = RenameRule::from_str(&lit_str.value()) else {
+                                let rules: Vec<String> = RENAME_RULES
+                                    .iter()
+                                    .map(|rule| format!("`{rule}`"))
+                                    .collect();
+                                return Err(syn::Error::new(
+                                    lit_str.span(),
+                                    format!(
+                                        "unknown rename rule, expected one of {}",
+                                        rules.join(", ")
+                                    ),
+                                ));
+                            };
+                            debug_attribute.rename_all = Some((lit_str, rule));
+                            Ok(())
+                        } else if meta.path.is_ident("redact") {
+                            let mut redact = Redact {
+                                key: meta.path.get_ident().cloned().expect("redact ident"),
+                                placeholder: None,
+                                partial: None,
+                            };
+                            if meta.input.peek(syn::Token![=]) {
+                                redact.placeholder = Some(meta.value()?.parse()?);
+                            } else if meta.input.peek(syn::token::Paren) {
+                                meta.parse_nested_meta(|meta| {
+                                    if meta.path.is_ident("partial") {
+                                        redact.partial = Some(meta.value()?.parse()?);
+                                        Ok(())
+                                    } else if meta.path.is_ident("placeholder") {
+                                        redact.placeholder = Some(meta.value()?.parse()?);
+                                        Ok(())
+                                    } else {
+                                        Err(meta.error("expected `partial` or `placeholder`"))
+                                    }
+                                })?;
+                            }
+                            debug_attribute.redact = Some(redact);
+                            Ok(())
+                        } else if meta.path.is_ident("with") {
+                            debug_attribute.with = Some(parse_fn_path(&meta)?);
+                            Ok(())
+                        } else if meta.path.is_ident("skip_if") {
+                            debug_attribute.skip_if = Some(parse_fn_path(&meta)?);
+                            Ok(())
+                        } else if meta.path.is_ident("skip_defaults") {
+                            debug_attribute.skip_defaults = meta.path.get_ident().cloned();
+                            Ok(())
+                        } else if meta.path.is_ident("flatten") {
+                            debug_attribute.flatten = meta.path.get_ident().cloned();
+                            Ok(())
+                        } else if meta.path.is_ident("transparent") {
+                            debug_attribute.transparent = meta.path.get_ident().cloned();
+                            Ok(())
+                        } else if meta.path.is_ident("visit") {
+                            debug_attribute.visit = meta.path.get_ident().cloned();
+                            Ok(())
+                        } else if meta.path.is_ident("limit") {
+                            debug_attribute.limit = Some(meta.value()?.parse()?);
+                            Ok(())
+                        } else if meta.path.is_ident("max_len") {
+                            debug_attribute.max_len = Some(meta.value()?.parse()?);
+                            Ok(())
+                        } else if PRESETS.iter().any(|preset| meta.path.is_ident(preset)) {
+                            if let Some(preset) = &debug_attribute.preset {
+                                return Err(meta.error(format!(
+                                    "`{}` can't be combined with `{}`",
+                                    meta.path.get_ident().expect("preset ident"),
+                                    preset
+                                )));
+                            }
+                            debug_attribute.preset = meta.path.get_ident().cloned();
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new(
+                                meta.path.span(),
+                                "expected one of `bound`, `skip`, `rename`, `rename_all`, `redact`, `with`, `limit`, `max_len`, `hex`, `bin`, `octal`, `bytes`, `skip_if`, `skip_defaults`, `flatten`, `transparent` or `visit`",
+                            ))
+                        }
+                    })?;
+                }
+                syn::Meta::NameValue(meta_name_value) => {
+                    if let syn::Expr::Lit(ref lit) = &meta_name_value.value {
+                        if let syn::Lit::Str(litstr) = &lit.lit {
+                            debug_attribute.debug = Some(litstr.clone());
+                        } else {
+                            return Err(expected_name_value::<D>(attr));
+                        }
+                    } else {
+                        return Err(expected_name_value::<D>(attr));
+                    };
+                }
+            }
+        }
+        Ok(debug_attribute)
+    }
+}
+
+// Both `key = path::to::fn` and `key = "path::to::fn"` are fine
+fn parse_fn_path(meta: &syn::meta::ParseNestedMeta) -> syn::Result<syn::ExprPath> {
+    let value = meta.value()?;
+    if value.peek(syn::LitStr) {
+        value.parse::<syn::LitStr>()?.parse()
+    } else {
+        value.parse()
+    }
+}
+
+fn expected_name_value<D: DeriveTrait>(attr: &syn::Attribute) -> syn::Error {
+    syn::Error::new(
+        attr.span(),
+        format!("expected `{} = \"...\"`", D::ATTRIBUTE),
+    )
+}
+
+impl<D: DeriveTrait> CustomDebugAttribute<D> {
+    // The formatting traits the field's value is printed through, when not in a struct or
+    // variant format string
+    fn value_traits(&self) -> Vec<syn::Path> {
+        if let Some(Redact {
+            partial: Some(_), ..
+        }) = &self.redact
+        {
+            // The shown end of the value comes from its `to_string()`
+            vec![parse_quote!(std::fmt::Display)]
+        } else if self.skip.is_some()
+            || self.flatten.is_some()
+            || self.redact.is_some()
+            || self.with.is_some()
+            || self.max_len.is_some()
+        {
+            vec![]
+        } else if let Some(preset) = &self.preset {
+            preset_trait(preset).into_iter().collect()
+        } else if let Some(debug_fmt) = &self.debug {
+            // A field's own format string decides which trait its value goes through
+            placeholders(&debug_fmt.value())
+                .iter()
+                .map(|placeholder| spec_trait(&placeholder.spec))
+                .collect()
+        } else {
+            vec![D::path()]
+        }
+    }
+
+    // The traits the whole field type needs, rather than what it's made of, when its value is
+    // printed through one of the wrappers in `helpers` or, with `visit`, visited
+    fn type_traits(&self, visit: bool) -> Vec<syn::Path> {
+        if self.flatten.is_some() && visit {
+            vec![parse_quote!(::derive_debug::FieldVisit)]
+        } else if self.flatten.is_some() {
+            vec![parse_quote!(::derive_debug::DebugFields)]
+        } else if self.max_len.is_some() {
+            vec![parse_quote!(std::convert::AsRef<str>)]
+        } else if self.preset.as_ref().is_some_and(|preset| preset == "bytes") {
+            vec![parse_quote!(std::convert::AsRef<[u8]>)]
+        } else {
+            vec![]
+        }
+    }
+
+    // Rejects the keys that don't apply in `context`, eg. `skip` on a struct
+    fn check_context(&self, context: AttributeContext) -> syn::Result<()> {
+        use AttributeContext::*;
+
+        let field = matches!(context, NamedField | UnnamedField);
+        // Without a field by field fallback the only thing fields are good for is the format
+        // string, most keys don't mean anything then
+        if !D::FIELDS_FALLBACK {
+            let unsupported = [
+                self.debug
+                    .as_ref()
+                    .filter(|_| field)
+                    .map(|debug| debug.span()),
+                self.skip.as_ref().map(|skip| skip.span()),
+                self.rename.as_ref().map(|rename| rename.span()),
+                self.rename_all
+                    .as_ref()
+                    .map(|(rename_all, _)| rename_all.span()),
+                self.redact.as_ref().map(|redact| redact.key.span()),
+                self.limit.as_ref().map(|limit| limit.span()),
+                self.max_len.as_ref().map(|max_len| max_len.span()),
+                self.preset.as_ref().map(|preset| preset.span()),
+                self.skip_if.as_ref().map(|skip_if| skip_if.span()),
+                self.skip_defaults
+                    .as_ref()
+                    .map(|skip_defaults| skip_defaults.span()),
+                self.with.as_ref().map(|with| with.span()),
+                self.flatten.as_ref().map(|flatten| flatten.span()),
+                self.transparent
+                    .as_ref()
+                    .map(|transparent| transparent.span()),
+                self.visit.as_ref().map(|visit| visit.span()),
+            ];
+            if let Some(span) = unsupported.into_iter().flatten().next() {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "only `bound` and struct or variant format strings are accepted in `#[{}]`",
+                        D::ATTRIBUTE
+                    ),
+                ));
+            }
+        }
+        if let Some(bound) = self.bound.as_ref().filter(|_| context == Variant) {
+            return Err(syn::Error::new(
+                bound.span(),
+                "`bound` only accepted on structs, enums and fields",
+            ));
+        }
+        if let Some(skip) = self.skip.as_ref().filter(|_| !field) {
+            return Err(syn::Error::new(
+                skip.span(),
+                "`skip` only accepted on fields",
+            ));
+        }
+        if let Some(redact) = &self.redact {
+            if !field {
+                return Err(syn::Error::new(
+                    redact.key.span(),
+                    "`redact` only accepted on fields",
+                ));
+            }
+        }
+        if let Some(skip_if) = self.skip_if.as_ref().filter(|_| !field) {
+            return Err(syn::Error::new(
+                skip_if.span(),
+                "`skip_if` only accepted on fields",
+            ));
+        }
+        if let Some(skip_if) = self.skip_if.as_ref().filter(|_| self.skip.is_some()) {
+            return Err(syn::Error::new(
+                skip_if.span(),
+                "`skip_if` can't be combined with `skip`",
+            ));
+        }
+        if let Some(skip_defaults) = self.skip_defaults.as_ref().filter(|_| field) {
+            return Err(syn::Error::new(
+                skip_defaults.span(),
+                "`skip_defaults` only accepted on structs, enums and variants",
+            ));
+        }
+        if let Some(flatten) = self.flatten.as_ref().filter(|_| context != NamedField) {
+            return Err(syn::Error::new(
+                flatten.span(),
+                "`flatten` only accepted on named fields",
+            ));
+        }
+        if let Some(transparent) = self.transparent.as_ref().filter(|_| context != Container) {
+            return Err(syn::Error::new(
+                transparent.span(),
+                "`transparent` only accepted on structs",
+            ));
+        }
+        if let Some(visit) = self.visit.as_ref().filter(|_| context != Container) {
+            return Err(syn::Error::new(
+                visit.span(),
+                "`visit` only accepted on structs and enums",
+            ));
+        }
+        if let Some(with) = self.with.as_ref().filter(|_| !field) {
+            return Err(syn::Error::new(
+                with.span(),
+                "`with` only accepted on fields",
+            ));
+        }
+        if let Some(limit) = self.limit.as_ref().filter(|_| !field) {
+            return Err(syn::Error::new(
+                limit.span(),
+                "`limit` only accepted on fields",
+            ));
+        }
+        if let Some(max_len) = self.max_len.as_ref().filter(|_| !field) {
+            return Err(syn::Error::new(
+                max_len.span(),
+                "`max_len` only accepted on fields",
+            ));
+        }
+        if let Some(preset) = self.preset.as_ref().filter(|_| !field) {
+            return Err(syn::Error::new(
+                preset.span(),
+                format!("`{}` only accepted on fields", preset),
+            ));
+        }
+        // Each of these decides on its own how the value is printed
+        let formats: Vec<(&str, proc_macro2::Span)> = [
+            self.debug
+                .as_ref()
+                .map(|debug| ("a format string", debug.span())),
+            self.with.as_ref().map(|with| ("`with`", with.span())),
+            self.redact
+                .as_ref()
+                .map(|redact| ("`redact`", redact.key.span())),
+            self.skip.as_ref().map(|skip| ("`skip`", skip.span())),
+            self.limit.as_ref().map(|limit| ("`limit`", limit.span())),
+            self.max_len
+                .as_ref()
+                .map(|max_len| ("`max_len`", max_len.span())),
+            self.preset
+                .as_ref()
+                .map(|preset| ("a numeric preset", preset.span())),
+            self.flatten
+                .as_ref()
+                .map(|flatten| ("`flatten`", flatten.span())),
+            self.transparent
+                .as_ref()
+                .map(|transparent| ("`transparent`", transparent.span())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if let [(first, _), (second, span), ..] = formats[..] {
+            return Err(syn::Error::new(
+                span,
+                format!("{second} can't be combined with {first}"),
+            ));
+        }
+        if let Some(rename) = self
+            .rename
+            .as_ref()
+            .filter(|_| !matches!(context, NamedField | Variant))
+        {
+            return Err(syn::Error::new(
+                rename.span(),
+                "`rename` only accepted on named fields and variants",
+            ));
+        }
+        if let Some((rename_all, _)) = self.rename_all.as_ref().filter(|_| field) {
+            return Err(syn::Error::new(
+                rename_all.span(),
+                "`rename_all` only accepted on structs, enums and variants",
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct CustomDebugField<D> {
+    field: syn::Field,
+    attr: CustomDebugAttribute<D>,
+}
+
+impl<D: DeriveTrait> CustomDebugField<D> {
+    fn parse_named(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attr: CustomDebugAttribute<D> = input.parse()?;
+        attr.check_context(AttributeContext::NamedField)?;
+        Ok(CustomDebugField {
+            field: syn::Field::parse_named(input)?,
+            attr,
+        })
+    }
+
+    // The expression `.field()` is given for the field bound to `binding`
+    fn debug_value(&self, binding: &syn::Ident) -> proc_macro2::TokenStream {
+        if let Some(redact) = &self.attr.redact {
+            let placeholder = redact
+                .placeholder
+                .as_ref()
+                .map_or_else(|| "<redacted>".to_owned(), syn::LitStr::value);
+            if let Some(partial) = &redact.partial {
+                // Values no longer than what would be shown are hidden entirely
+                quote! {
+                    &format_args!("{}{}", #placeholder, {
+                        let value = std::string::ToString::to_string(#binding);
+                        let chars = value.chars().count();
+                        if chars > #partial {
+                            value.chars().skip(chars - #partial).collect()
+                        } else {
+                            std::string::String::new()
+                        }
+                    })
+                }
+            } else {
+                quote! { &format_args!("{}", #placeholder) }
+            }
+        } else if let Some(with) = &self.attr.with {
+            quote! { &DebugWith(#binding, #with) }
+        } else if let Some(limit) = &self.attr.limit {
+            let (_, value) = self.dereferenced(binding);
+            quote! { &DebugLimit(#value, #limit) }
+        } else if let Some(max_len) = &self.attr.max_len {
+            quote! { &DebugMaxLen(#binding, #max_len) }
+        } else if let Some(preset) = &self.attr.preset {
+            let Some(trait_path) = preset_trait(preset) else {
+                return quote! { &DebugBytes(#binding) };
+            };
+            let (ty, value) = self.dereferenced(binding);
+            match element_type(ty) {
+                Some(elem) => quote! { &DebugEach(#value, <#elem as #trait_path>::fmt) },
+                None => quote! { &DebugWith(#value, <#ty as #trait_path>::fmt) },
+            }
+        } else if let Some(ref debug_fmt) = &self.attr.debug {
+            quote! { &format_args!(#debug_fmt, #binding) }
+        } else {
+            quote! { #binding }
+        }
+    }
+
+    // The field's type and value with references looked through, `&&[u8]` doesn't iterate but
+    // `&[u8]` does
+    fn dereferenced(&self, binding: &syn::Ident) -> (&syn::Type, proc_macro2::TokenStream) {
+        let mut ty = &self.field.ty;
+        let mut value = quote!(#binding);
+        while let syn::Type::Reference(reference) = ty {
+            ty = &reference.elem;
+            value = quote!(*#value);
+        }
+        (ty, value)
+    }
+
+    // The name printed for a named field
+    fn debug_name(&self, rename_all: Option<RenameRule>) -> String {
+        let ident = self
+            .field
+            .ident
+            .as_ref()
+            .expect("field ident")
+            .unraw()
+            .to_string();
+        if let Some(rename) = &self.attr.rename {
+            rename.value()
+        } else if let Some(rule) = rename_all {
+            rule.apply_to_field(&ident)
+        } else {
+            ident
+        }
+    }
+
+    fn parse_unnamed(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attr: CustomDebugAttribute<D> = input.parse()?;
+        attr.check_context(AttributeContext::UnnamedField)?;
+        Ok(CustomDebugField {
+            field: syn::Field::parse_unnamed(input)?,
+            attr,
+        })
+    }
+}
+
+impl<D: DeriveTrait> CustomDebugInput<D> {
+    pub fn fmt_impl(&self) -> proc_macro2::TokenStream {
+        let name = &self.ident;
+
+        let trait_path = D::path();
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        // The type's own where clause still applies, the bounds are added to it
+        let existing = where_clause
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        let predicates = if let Some(bound) = self.attr.bound.as_ref() {
+            bound.iter().map(|predicate| quote!(#predicate)).collect()
+        } else {
+            self.inferred_bounds(false)
+        };
+        let impl_decl = quote! {
+            impl #impl_generics #trait_path for #name #ty_generics
+            where
+                #(#existing,)*
+                #(#predicates,)*
+        };
+
+        let rename_all = self.attr.rename_all.as_ref().map(|(_, rule)| *rule);
+        let arms = match &self.data {
+            CustomDebugData::Struct { fields, format, .. } => {
+                let (pattern, body) = if let Some(format) = format {
+                    (fields.pattern(quote!(Self), &format.used()), format.body())
+                } else if self.attr.transparent.is_some() {
+                    let (field, binding) = fields
+                        .iter()
+                        .zip(fields.bindings())
+                        .find(|(f, _)| f.attr.skip.is_none())
+                        .expect("transparent field");
+                    let value = field.debug_value(&binding);
+                    (
+                        fields.pattern(quote!(Self), &fields.shown()),
+                        quote! { std::fmt::Debug::fmt(#value, f) },
+                    )
+                } else {
+                    let name = name.unraw().to_string();
+                    (
+                        fields.pattern(quote!(Self), &fields.shown()),
+                        fields.debug_body(&name, rename_all, self.attr.skip_defaults.is_some()),
+                    )
+                };
+                vec![quote! { #pattern => #body, }]
+            }
+            CustomDebugData::Enum { variants, .. } => variants
+                .iter()
+                .map(|variant| {
+                    let variant_name = &variant.ident;
+                    let path = quote!(Self::#variant_name);
+                    let fields = &variant.fields;
+                    let (pattern, body) = if let Some(format) = &variant.format {
+                        (fields.pattern(path, &format.used()), format.body())
+                    } else {
+                        let name = variant.debug_name(rename_all);
+                        (
+                            fields.pattern(path, &fields.shown()),
+                            fields.debug_body(
+                                &name,
+                                variant.attr.rename_all.as_ref().map(|(_, rule)| *rule),
+                                self.attr.skip_defaults.is_some()
+                                    || variant.attr.skip_defaults.is_some(),
+                            ),
+                        )
+                    };
+                    quote! { #pattern => #body, }
+                })
+                .collect(),
+        };
+        // A reference to an enum without variants isn't known to be uninhabited, its referent is
+        let body = if arms.is_empty() {
+            quote! {
+                let _ = f;
+                match *self {}
+            }
+        } else {
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        };
+        let helpers = self.helpers();
+        let debug_fields = self.debug_fields_impl(&helpers, &predicates);
+        let visit_impl = self.visit_impl(&helpers);
+        quote_spanned! { self.ident.span() =>
+            #impl_decl {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    #helpers
+                    #body
+                }
+            }
+
+            #debug_fields
+
+            #visit_impl
+        }
+    }
+
+    // With `#[debug(visit)]` the fields `fmt` would print are also handed out one by one
+    // through `derive_debug::FieldVisit`. Format strings on the whole struct or variant and
+    // `transparent` are left out of it, every field is visited under its own name
+    fn visit_impl(&self, helpers: &proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+        self.attr.visit.as_ref()?;
+        let name = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let existing = where_clause
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        // Fields are visited one by one even when `fmt` uses a format string, so they get
+        // bounds of their own rather than the ones of the string
+        let predicates = if let Some(bound) = self.attr.bound.as_ref() {
+            bound.iter().map(|predicate| quote!(#predicate)).collect()
+        } else {
+            self.inferred_bounds(true)
+        };
+        let rename_all = self.attr.rename_all.as_ref().map(|(_, rule)| *rule);
+        let skip_defaults = self.attr.skip_defaults.is_some();
+        let arms: Vec<proc_macro2::TokenStream> = match &self.data {
+            CustomDebugData::Struct { fields, .. } => {
+                let pattern = fields.pattern(quote!(Self), &fields.shown());
+                let statements = fields.field_statements(rename_all, skip_defaults, true);
+                vec![quote! { #pattern => { #(#statements)* } }]
+            }
+            CustomDebugData::Enum { variants, .. } => variants
+                .iter()
+                .map(|variant| {
+                    let variant_name = &variant.ident;
+                    let fields = &variant.fields;
+                    let pattern = fields.pattern(quote!(Self::#variant_name), &fields.shown());
+                    let statements = fields.field_statements(
+                        variant.attr.rename_all.as_ref().map(|(_, rule)| *rule),
+                        skip_defaults || variant.attr.skip_defaults.is_some(),
+                        true,
+                    );
+                    quote! { #pattern => { #(#statements)* } }
+                })
+                .collect(),
+        };
+        let body = if arms.is_empty() {
+            quote! {
+                let _ = visitor;
+                match *self {}
+            }
+        } else {
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        };
+        Some(quote_spanned! { self.ident.span() =>
+            impl #impl_generics ::derive_debug::FieldVisit for #name #ty_generics
+            where
+                #(#existing,)*
+                #(#predicates,)*
+            {
+                fn visit_fields(&self, visitor: &mut dyn ::derive_debug::FieldVisitor) {
+                    #helpers
+                    #body
+                }
+            }
+        })
+    }
+
+    // Structs printed field by field also implement the hidden `derive_debug::DebugFields`,
+    // adding those fields to any `DebugStruct`, which is what a `#[debug(flatten)]` field of
+    // their type calls
+    fn debug_fields_impl(
+        &self,
+        helpers: &proc_macro2::TokenStream,
+        predicates: &[proc_macro2::TokenStream],
+    ) -> Option<proc_macro2::TokenStream> {
+        let CustomDebugData::Struct {
+            fields: fields @ CustomDebugFields::Named { .. },
+            format: None,
+            ..
+        } = &self.data
+        else {
+            return None;
+        };
+        if !D::FIELDS_FALLBACK || self.attr.transparent.is_some() {
+            return None;
+        }
+        let name = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let existing = where_clause
+            .into_iter()
+            .flat_map(|where_clause| &where_clause.predicates);
+        let pattern = fields.pattern(quote!(Self), &fields.shown());
+        let statements = fields.field_statements(
+            self.attr.rename_all.as_ref().map(|(_, rule)| *rule),
+            self.attr.skip_defaults.is_some(),
+            false,
+        );
+        Some(quote! {
+            impl #impl_generics ::derive_debug::DebugFields for #name #ty_generics
+            where
+                #(#existing,)*
+                #(#predicates,)*
+            {
+                fn debug_fields(&self, debug: &mut std::fmt::DebugStruct<'_, '_>) {
+                    #helpers
+                    match self {
+                        #pattern => {
+                            #(#statements)*
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    // Every field of the struct, or of all the variants of the enum
+    fn fields(&self) -> Vec<&CustomDebugField<D>> {
+        match &self.data {
+            CustomDebugData::Struct { fields, .. } => fields.iter().collect(),
+            CustomDebugData::Enum { variants, .. } => variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter())
+                .collect(),
+        }
+    }
+
+    // The wrapper types the field attributes format values through, defined inside `fmt` when
+    // some field needs them
+    fn helpers(&self) -> proc_macro2::TokenStream {
+        let fields = self.fields();
+        let mut helpers = proc_macro2::TokenStream::new();
+        let presets: Vec<&syn::Ident> = fields
+            .iter()
+            .filter_map(|f| f.attr.preset.as_ref())
+            .collect();
+        if fields.iter().any(|f| f.attr.with.is_some()) || presets.iter().any(|p| *p != "bytes") {
+            // Lets a `#[debug(with = ...)]` function, or a fmt trait for presets, stand in for the
+            // field's `Debug` impl
+            helpers.extend(quote! {
+                struct DebugWith<'a, T: ?std::marker::Sized>(
+                    &'a T,
+                    fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+                );
+                impl<T: ?std::marker::Sized> std::fmt::Debug for DebugWith<'_, T> {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        (self.1)(self.0, f)
+                    }
+                }
+            });
+        }
+        if presets.iter().any(|p| *p != "bytes") {
+            // Formats each element of a sequence through the function, ie. for `#[debug(hex)]`
+            helpers.extend(quote! {
+                struct DebugEach<'a, T: ?std::marker::Sized, E>(
+                    &'a T,
+                    fn(&E, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+                );
+                impl<'a, T: ?std::marker::Sized, E: 'a> std::fmt::Debug for DebugEach<'a, T, E>
+                where
+                    &'a T: std::iter::IntoIterator<Item = &'a E>,
+                {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.debug_list()
+                            .entries(
+                                std::iter::IntoIterator::into_iter(self.0)
+                                    .map(|elem| DebugWith(elem, self.1)),
+                            )
+                            .finish()
+                    }
+                }
+            });
+        }
+        if presets.iter().any(|p| *p == "bytes") {
+            helpers.extend(quote! {
+                struct DebugBytes<'a, T: ?std::marker::Sized>(&'a T);
+                impl<T> std::fmt::Debug for DebugBytes<'_, T>
+                where
+                    T: ?std::marker::Sized + std::convert::AsRef<[u8]>,
+                {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        if f.alternate() {
+                            f.write_str("0x")?;
+                        }
+                        for byte in self.0.as_ref() {
+                            write!(f, "{:02x}", byte)?;
+                        }
+                        std::result::Result::Ok(())
+                    }
+                }
+            });
+        }
+        if fields.iter().any(|f| f.attr.limit.is_some()) {
+            helpers.extend(quote! {
+                struct DebugLimit<'a, T: ?std::marker::Sized>(&'a T, usize);
+                impl<'a, T: ?std::marker::Sized> std::fmt::Debug for DebugLimit<'a, T>
+                where
+                    &'a T: std::iter::IntoIterator,
+                    <&'a T as std::iter::IntoIterator>::Item: std::fmt::Debug,
+                {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        let mut iter = std::iter::IntoIterator::into_iter(self.0);
+                        let mut list = f.debug_list();
+                        list.entries(iter.by_ref().take(self.1));
+                        let more = iter.count();
+                        if more > 0 {
+                            list.entry(&format_args!("\u{2026} ({} more)", more));
+                        }
+                        list.finish()
+                    }
+                }
+            });
+        }
+        if fields.iter().any(|f| f.attr.max_len.is_some()) {
+            helpers.extend(quote! {
+                struct DebugMaxLen<'a, T: ?std::marker::Sized>(&'a T, usize);
+                impl<T> std::fmt::Debug for DebugMaxLen<'_, T>
+                where
+                    T: ?std::marker::Sized + std::convert::AsRef<str>,
+                {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        let value = self.0.as_ref();
+                        match value.char_indices().nth(self.1) {
+                            std::option::Option::Some((end, _)) => {
+                                std::fmt::Debug::fmt(&value[..end], f)?;
+                                write!(f, "\u{2026} ({} more)", value[end..].chars().count())
+                            }
+                            std::option::Option::None => std::fmt::Debug::fmt(value, f),
+                        }
+                    }
+                }
+            });
+        }
+        helpers
+    }
+
+    // Each struct or variant with its format string, if it has one, and whether it leaves out
+    // fields holding their default value
+    fn formats(&self) -> Vec<(&CustomDebugFields<D>, Option<&FormatString>, bool)> {
+        let skip_defaults = self.attr.skip_defaults.is_some();
+        match &self.data {
+            CustomDebugData::Struct { fields, format, .. } => {
+                vec![(fields, format.as_ref(), skip_defaults)]
+            }
+            CustomDebugData::Enum { variants, .. } => variants
+                .iter()
+                .map(|variant| {
+                    (
+                        &variant.fields,
+                        variant.format.as_ref(),
+                        skip_defaults || variant.attr.skip_defaults.is_some(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    // The bounds needed to format every field the way it's going to be, a field's own
+    // `bound` replacing whatever would be inferred for it. With `visit` they're the bounds
+    // for visiting the fields, which ignores struct and variant format strings
+    fn inferred_bounds(&self, visit: bool) -> Vec<proc_macro2::TokenStream> {
+        let mut inference = BoundInference::new(&self.generics);
+        for (fields, format, skip_defaults) in self.formats() {
+            let format = format.filter(|_| !visit);
+            for (position, f) in fields.iter().enumerate() {
+                // Comparing against the default needs the whole field type to support it
+                if skip_defaults
+                    && format.is_none()
+                    && f.attr.skip.is_none()
+                    && f.attr.skip_if.is_none()
+                {
+                    inference.add_type(&f.field.ty, &parse_quote!(std::cmp::PartialEq));
+                    inference.add_type(&f.field.ty, &parse_quote!(std::default::Default));
+                }
+                if let Some(bound) = &f.attr.bound {
+                    for predicate in bound {
+                        inference.push(quote_spanned! {f.field.span() =>
+                            #predicate
+                        });
+                    }
+                    continue;
+                }
+                let traits = match format {
+                    Some(format) => format.traits[position].clone(),
+                    None => {
+                        for trait_path in f.attr.type_traits(visit) {
+                            inference.add_type(&f.field.ty, &trait_path);
+                        }
+                        f.attr.value_traits()
+                    }
+                };
+                for trait_path in &traits {
+                    inference.add(&f.field.ty, trait_path);
+                }
+            }
+        }
+        inference.predicates()
+    }
+}
@@ -0,0 +1,34 @@
+// The formatting trait a derive implements. `CustomDebug` and `CustomDisplay` share the attribute
+// parsing and bound inference, these are the bits that differ between them
+
+pub trait DeriveTrait {
+    // Name of the helper attribute, ie. `debug` for `#[debug(...)]`
+    const ATTRIBUTE: &'static str;
+    // Whether a struct or variant without a format string is printed field by field, otherwise
+    // only unit structs and variants can go without one and are printed by name
+    const FIELDS_FALLBACK: bool;
+
+    fn path() -> syn::Path;
+}
+
+pub struct Debug;
+
+impl DeriveTrait for Debug {
+    const ATTRIBUTE: &'static str = "debug";
+    const FIELDS_FALLBACK: bool = true;
+
+    fn path() -> syn::Path {
+        syn::parse_quote!(std::fmt::Debug)
+    }
+}
+
+pub struct Display;
+
+impl DeriveTrait for Display {
+    const ATTRIBUTE: &'static str = "display";
+    const FIELDS_FALLBACK: bool = false;
+
+    fn path() -> syn::Path {
+        syn::parse_quote!(std::fmt::Display)
+    }
+}
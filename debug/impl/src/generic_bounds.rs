@@ -0,0 +1,122 @@
+use quote::quote_spanned;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+// Collects the where predicates a derived impl needs, from the types of the fields it formats.
+// Only the type params, and associated types of them, that actually end up formatted get a
+// bound, ie. `&'a T` needs `T: Debug` while `PhantomData<T>` or `fn(T) -> U` need nothing
+pub struct BoundInference<'a> {
+    params: Vec<&'a syn::Ident>,
+    predicates: Vec<proc_macro2::TokenStream>,
+}
+
+impl<'a> BoundInference<'a> {
+    pub fn new(generics: &'a syn::Generics) -> Self {
+        BoundInference {
+            params: generics.type_params().map(|param| &param.ident).collect(),
+            predicates: vec![],
+        }
+    }
+
+    // Bounds what `ty` needs to be formatted through `trait_path`
+    pub fn add(&mut self, ty: &syn::Type, trait_path: &syn::Path) {
+        let mut visitor = ParamVisitor {
+            params: &self.params,
+            found: vec![],
+        };
+        visitor.visit_type(ty);
+        for found in visitor.found {
+            self.push(quote_spanned! {found.span() =>
+                #found: #trait_path
+            });
+        }
+    }
+
+    // Bounds `ty` itself rather than what it's made of, if it mentions a type param at all
+    pub fn add_type(&mut self, ty: &syn::Type, trait_path: &syn::Path) {
+        let mut visitor = ParamVisitor {
+            params: &self.params,
+            found: vec![],
+        };
+        visitor.visit_type(ty);
+        if !visitor.found.is_empty() {
+            self.push(quote_spanned! {ty.span() =>
+                #ty: #trait_path
+            });
+        }
+    }
+
+    // A predicate given by the user, which is kept as is
+    pub fn push(&mut self, predicate: proc_macro2::TokenStream) {
+        let predicate_str = predicate.to_string();
+        if !self
+            .predicates
+            .iter()
+            .any(|existing| existing.to_string() == predicate_str)
+        {
+            self.predicates.push(predicate);
+        }
+    }
+
+    pub fn predicates(self) -> Vec<proc_macro2::TokenStream> {
+        self.predicates
+    }
+}
+
+// Finds the type params and projections of them, ie. `T::Item` or `<T as Trait>::Item`, that a
+// value of the visited type formats
+struct ParamVisitor<'p, 'a> {
+    params: &'p [&'a syn::Ident],
+    found: Vec<syn::TypePath>,
+}
+
+impl ParamVisitor<'_, '_> {
+    fn mentions_param(&self, ty: &syn::Type) -> bool {
+        let mut visitor = ParamVisitor {
+            params: self.params,
+            found: vec![],
+        };
+        visitor.visit_type(ty);
+        !visitor.found.is_empty()
+    }
+}
+
+impl<'ast> Visit<'ast> for ParamVisitor<'_, '_> {
+    fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+        if let Some(qself) = &ty.qself {
+            if self.mentions_param(&qself.ty) {
+                self.found.push(ty.clone());
+            }
+            return;
+        }
+        let path = &ty.path;
+        if path.leading_colon.is_none()
+            && path
+                .segments
+                .first()
+                .is_some_and(|segment| self.params.contains(&&segment.ident))
+        {
+            self.found.push(ty.clone());
+            return;
+        }
+        // `PhantomData<T>` is `Debug` whatever `T` is
+        if path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData")
+        {
+            return;
+        }
+        visit::visit_type_path(self, ty);
+    }
+
+    // Function pointers are formatted as addresses, their signature doesn't matter
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    // Trait objects and `impl Trait` don't implement anything through their type arguments
+    fn visit_type_trait_object(&mut self, _: &'ast syn::TypeTraitObject) {}
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {}
+
+    fn visit_type_macro(&mut self, _: &'ast syn::TypeMacro) {}
+}
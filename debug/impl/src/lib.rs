@@ -0,0 +1,26 @@
+use quote::quote;
+
+use crate::custom_debug::CustomDebugInput;
+
+mod custom_debug;
+mod derive_trait;
+mod generic_bounds;
+mod rename_rule;
+
+#[proc_macro_derive(CustomDebug, attributes(debug))]
+pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    // eprintln!("INPUT: {}", input);
+    let input = syn::parse_macro_input!(input as CustomDebugInput<derive_trait::Debug>);
+    let debug_impl = input.fmt_impl();
+    let stream = quote! {
+        #debug_impl
+    };
+    // eprintln!("OUTPUT: {stream}");
+    stream.into()
+}
+
+#[proc_macro_derive(CustomDisplay, attributes(display))]
+pub fn derive_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as CustomDebugInput<derive_trait::Display>);
+    input.fmt_impl().into()
+}
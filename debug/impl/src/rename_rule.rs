@@ -0,0 +1,95 @@
+// The case conventions accepted by `#[debug(rename_all = "...")]`, named like serde's so the
+// same spelling works on types deriving both
+
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+pub const RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+impl RenameRule {
+    pub fn from_str(rule: &str) -> Option<Self> {
+        match rule {
+            "lowercase" => Some(RenameRule::Lower),
+            "UPPERCASE" => Some(RenameRule::Upper),
+            "PascalCase" => Some(RenameRule::Pascal),
+            "camelCase" => Some(RenameRule::Camel),
+            "snake_case" => Some(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnake),
+            "kebab-case" => Some(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(RenameRule::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    // Field names are expected to be snake_case to begin with
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let mut renamed = String::new();
+                let mut capitalize = matches!(self, RenameRule::Pascal);
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        renamed.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        renamed.push(ch);
+                    }
+                }
+                renamed
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    // Variant names are expected to be PascalCase to begin with
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_owned(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            RenameRule::Snake
+            | RenameRule::ScreamingSnake
+            | RenameRule::Kebab
+            | RenameRule::ScreamingKebab => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                RenameRule::apply_to_field(self, &snake)
+            }
+        }
+    }
+}
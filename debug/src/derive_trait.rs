@@ -1,6 +0,0 @@
-// struct NameStruct;
-// impl std::fmt::Debug for NameStruct {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-
-//     }
-// }
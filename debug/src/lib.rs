@@ -1,18 +1,42 @@
-use quote::quote;
+// Crates with the "proc-macro" crate type can only export procedural macros, so
+// the derives live in derive_debug-impl and are re-exported from here next to
+// the traits their generated code implements, the same split as bitfield.
+pub use derive_debug_impl::{CustomDebug, CustomDisplay};
 
-use crate::custom_debug::CustomDebugInput;
+use std::fmt::Debug;
 
-mod custom_debug;
-mod derive_trait;
+// Receives the fields of a value one by one, ie. to write them as key/value
+// pairs of a structured log line rather than one formatted string.
+pub trait FieldVisitor {
+    // The value is the one `Debug` would print for the field, after `skip`,
+    // `redact`, format strings and the like are applied
+    fn visit_field(&mut self, name: &str, value: &dyn Debug);
+}
+
+// Any closure taking a name and value works as a visitor
+impl<F> FieldVisitor for F
+where
+    F: FnMut(&str, &dyn Debug),
+{
+    fn visit_field(&mut self, name: &str, value: &dyn Debug) {
+        self(name, value)
+    }
+}
+
+// Implemented by `#[derive(CustomDebug)]` for structs printed field by field,
+// a `#[debug(flatten)]` field of such a struct adds its fields to the parent's
+// output through it. Not meant to be implemented or called by hand.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be flattened",
+    note = "only structs with named fields deriving `CustomDebug` can be flattened"
+)]
+pub trait DebugFields {
+    fn debug_fields(&self, debug: &mut std::fmt::DebugStruct<'_, '_>);
+}
 
-#[proc_macro_derive(CustomDebug, attributes(debug))]
-pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    eprintln!("INPUT: {}", input);
-    let input = syn::parse_macro_input!(input as CustomDebugInput);
-    let debug_impl = input.debug_impl();
-    let stream = quote! {
-        #debug_impl
-    };
-    eprintln!("OUTPUT: {stream}");
-    stream.into()
+// Implemented by `#[derive(CustomDebug)]` with `#[debug(visit)]` on the type.
+// Fields are visited in declaration order, tuple fields are named by position.
+pub trait FieldVisit {
+    fn visit_fields(&self, visitor: &mut dyn FieldVisitor);
 }
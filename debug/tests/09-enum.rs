@@ -0,0 +1,59 @@
+// The derive also works on enums, formatting each variant the way the std
+// derive would: struct variants through `debug_struct`, tuple variants through
+// `debug_tuple` and unit variants by name. Field attributes apply inside
+// variants just like on struct fields, and bounds are inferred from the fields
+// of every variant.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+pub enum Event<T, U: Trait> {
+    Connected {
+        peer: T,
+        #[debug = "0x{:04x}"]
+        port: u16,
+    },
+    Message(T, #[debug = "{:?} bytes"] usize),
+    Batch(Vec<U::Value>),
+    Closed,
+}
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Event<&str, Id>>();
+    assert_debug::<Never>();
+
+    let connected: Event<&str, Id> = Event::Connected {
+        peer: "localhost",
+        port: 8080,
+    };
+    assert_eq!(
+        format!("{:?}", connected),
+        r#"Connected { peer: "localhost", port: 0x1f90 }"#
+    );
+
+    let message: Event<&str, Id> = Event::Message("hello", 5);
+    assert_eq!(format!("{:?}", message), r#"Message("hello", 5 bytes)"#);
+
+    let batch: Event<&str, Id> = Event::Batch(vec![1, 2]);
+    assert_eq!(format!("{:?}", batch), "Batch([1, 2])");
+
+    let closed: Event<&str, Id> = Event::Closed;
+    assert_eq!(format!("{:?}", closed), "Closed");
+    assert_eq!(format!("{:#?}", closed), "Closed");
+}
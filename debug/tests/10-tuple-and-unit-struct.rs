@@ -0,0 +1,24 @@
+// Newtypes and other tuple structs are formatted through `debug_tuple`, unit
+// structs by their name, same as the std derive. Format strings work on tuple
+// fields too.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Id(#[debug = "{:#x}"] u64);
+
+#[derive(CustomDebug)]
+pub struct Pair<T>(T, pub &'static str);
+
+#[derive(CustomDebug)]
+pub struct Marker;
+
+fn main() {
+    assert_eq!(format!("{:?}", Id(255)), "Id(0xff)");
+    assert_eq!(format!("{:?}", Pair(1, "one")), r#"Pair(1, "one")"#);
+    assert_eq!(
+        format!("{:#?}", Pair(1, "one")),
+        "Pair(\n    1,\n    \"one\",\n)"
+    );
+    assert_eq!(format!("{:?}", Marker), "Marker");
+}
@@ -0,0 +1,52 @@
+// Fields marked #[debug(skip)] are left out of the output, which then ends in
+// `..` like `finish_non_exhaustive` does so it's clear something is missing.
+// #[debug(rename = "...")] changes the name a field or variant is printed
+// under, and #[debug(rename_all = "...")] renames all the fields of a struct
+// or variant, or all the variants of an enum, using serde's case names.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "camelCase")]
+pub struct Request {
+    request_id: u64,
+    #[debug(rename = "path")]
+    request_path: &'static str,
+    #[debug(skip)]
+    session_key: String,
+}
+
+#[derive(CustomDebug)]
+pub struct Handle(u32, #[debug(skip)] *const u8);
+
+#[derive(CustomDebug)]
+#[debug(rename_all = "snake_case")]
+pub enum State {
+    NotStarted,
+    #[debug(rename_all = "SCREAMING-KEBAB-CASE")]
+    InProgress { bytes_done: u64 },
+    #[debug(rename = "done")]
+    Finished(#[debug(skip)] Vec<u8>),
+}
+
+fn main() {
+    let request = Request {
+        request_id: 7,
+        request_path: "/",
+        session_key: "hunter2".to_owned(),
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { requestId: 7, path: "/", .. }"#
+    );
+
+    let handle = Handle(3, std::ptr::null());
+    assert_eq!(format!("{:?}", handle), "Handle(3, ..)");
+
+    assert_eq!(format!("{:?}", State::NotStarted), "not_started");
+    assert_eq!(
+        format!("{:?}", State::InProgress { bytes_done: 10 }),
+        "in_progress { BYTES-DONE: 10 }"
+    );
+    assert_eq!(format!("{:?}", State::Finished(vec![])), "done(..)");
+}
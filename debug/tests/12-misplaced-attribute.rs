@@ -0,0 +1,12 @@
+// Attribute keys that don't mean anything where they are written are an error
+// rather than silently ignored.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(skip)]
+pub struct Config {
+    max_retries: u32,
+}
+
+fn main() {}
@@ -0,0 +1,65 @@
+// Secrets held in a struct shouldn't end up in logs just because the struct
+// got printed. A field marked #[debug(redact)] prints a placeholder instead of
+// its value, #[debug(redact = "...")] picks the placeholder, and
+// #[debug(redact(partial = N))] also shows the last N characters of the
+// field's Display output, eg. to tell cards apart by their last digits.
+// Values no longer than N characters are hidden entirely.
+//
+// A generic field that's redacted needs no Debug bound, partially redacted it
+// needs Display.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Login {
+    user: &'static str,
+    #[debug(redact)]
+    password: String,
+    #[debug(redact = "***")]
+    token: String,
+    #[debug(redact(partial = 4))]
+    card_number: u64,
+    #[debug(redact(partial = 4, placeholder = "..."))]
+    pin: u16,
+}
+
+#[derive(CustomDebug)]
+pub struct ApiKey(#[debug(redact(partial = 2))] String);
+
+#[derive(CustomDebug)]
+pub struct Secret<T, U> {
+    #[debug(redact(partial = 2))]
+    value: T,
+    #[debug(redact)]
+    opaque: U,
+}
+
+pub struct Opaque;
+
+fn main() {
+    let login = Login {
+        user: "alice",
+        password: "hunter2".to_owned(),
+        token: "abcdef".to_owned(),
+        card_number: 4111111111111234,
+        pin: 1234,
+    };
+    assert_eq!(
+        format!("{:?}", login),
+        r#"Login { user: "alice", password: <redacted>, token: ***, card_number: <redacted>1234, pin: ... }"#
+    );
+
+    assert_eq!(
+        format!("{:?}", ApiKey("secret-key-42".to_owned())),
+        "ApiKey(<redacted>42)"
+    );
+
+    let secret = Secret {
+        value: 1234,
+        opaque: Opaque,
+    };
+    assert_eq!(
+        format!("{:?}", secret),
+        "Secret { value: <redacted>34, opaque: <redacted> }"
+    );
+}
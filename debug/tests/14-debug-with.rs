@@ -0,0 +1,52 @@
+// Some values need more than a format string to print usefully, like a byte
+// buffer as a hex dump. #[debug(with = path::to::function)] formats the field
+// with a function of the same shape as `Debug::fmt`, ie.
+// `fn(&T, &mut fmt::Formatter) -> fmt::Result`. The path may also be given as
+// a string.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+use std::time::Duration;
+
+mod format {
+    use std::fmt;
+
+    pub fn hex_dump<T: AsRef<[u8]>>(bytes: &T, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in bytes.as_ref() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+fn seconds(duration: &Duration, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}s", duration.as_secs())
+}
+
+#[derive(CustomDebug)]
+pub struct Packet {
+    #[debug(with = "format::hex_dump")]
+    payload: [u8; 4],
+    #[debug(with = seconds)]
+    timeout: Duration,
+    #[debug(with = opaque)]
+    extra: NotDebug,
+}
+
+pub struct NotDebug;
+
+fn opaque<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("_")
+}
+
+fn main() {
+    let packet = Packet {
+        payload: [0xde, 0xad, 0xbe, 0xef],
+        timeout: Duration::from_secs(30),
+        extra: NotDebug,
+    };
+    assert_eq!(
+        format!("{:?}", packet),
+        "Packet { payload: deadbeef, timeout: 30s, extra: _ }"
+    );
+}
@@ -0,0 +1,66 @@
+// A format string on the struct itself, #[debug("...")], prints the whole
+// value through it instead of `debug_struct`. Fields are referred to by name,
+// or by index in tuple structs, and can use any format spec. The same works on
+// enum variants, while the other variants keep the usual output. A field can
+// also give the width or precision of another, ie. `{name:>width$}`.
+//
+// On fields, #[debug("...")] is the same as #[debug = "..."].
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug("Point({x}, {y:.2})")]
+pub struct Point {
+    x: i32,
+    y: f64,
+}
+
+#[derive(CustomDebug)]
+#[debug("#{0:06x}")]
+pub struct Color(u32);
+
+#[derive(CustomDebug)]
+#[debug("[{name:>width$}|{ratio:.precision$}]")]
+pub struct Cell {
+    name: &'static str,
+    width: usize,
+    ratio: f64,
+    precision: usize,
+}
+
+#[derive(CustomDebug)]
+#[debug("{0:01$}")]
+pub struct Padded(u32, usize);
+
+#[derive(CustomDebug)]
+pub enum Metric {
+    #[debug("{name}={value:?}")]
+    Gauge {
+        name: &'static str,
+        value: Option<u64>,
+        unit: &'static str,
+    },
+    Counter(#[debug("{:+}")] i64),
+}
+
+fn main() {
+    assert_eq!(format!("{:?}", Point { x: 1, y: 2.5 }), "Point(1, 2.50)");
+    assert_eq!(format!("{:?}", Color(0xff8000)), "#ff8000");
+
+    let cell = Cell {
+        name: "id",
+        width: 4,
+        ratio: 0.5,
+        precision: 3,
+    };
+    assert_eq!(format!("{:?}", cell), "[  id|0.500]");
+    assert_eq!(format!("{:?}", Padded(7, 3)), "007");
+
+    let gauge = Metric::Gauge {
+        name: "cpu",
+        value: Some(42),
+        unit: "%",
+    };
+    assert_eq!(format!("{:?}", gauge), "cpu=Some(42)");
+    assert_eq!(format!("{:?}", Metric::Counter(3)), "Counter(+3)");
+}
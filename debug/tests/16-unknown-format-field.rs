@@ -0,0 +1,12 @@
+// Names in a struct level format string have to be fields of the struct.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug("Point({x}, {z})")]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
@@ -0,0 +1,56 @@
+// The crate also has a CustomDisplay derive, driven by the same kind of format
+// strings given in #[display("...")] on the struct or on each variant. Unit
+// structs and unit variants without one are displayed as their name. Type
+// parameters get a `Display` bound inferred, and #[display(bound = "...")]
+// works like it does for CustomDebug.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+#[derive(CustomDebug, CustomDisplay)]
+pub enum Error<E> {
+    #[display("failed to read {path}: {source}")]
+    Io {
+        path: &'static str,
+        source: E,
+    },
+    #[display("invalid header {0:?} at byte {1}")]
+    InvalidHeader(Vec<u8>, usize),
+    Timeout,
+}
+
+#[derive(CustomDisplay)]
+#[display("{major}.{minor}.{patch}")]
+pub struct Version {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+#[derive(CustomDisplay)]
+pub struct Unknown;
+
+fn main() {
+    let io: Error<&str> = Error::Io {
+        path: "/etc/hosts",
+        source: "permission denied",
+    };
+    assert_eq!(io.to_string(), "failed to read /etc/hosts: permission denied");
+    assert_eq!(
+        format!("{:?}", io),
+        r#"Io { path: "/etc/hosts", source: "permission denied" }"#
+    );
+
+    let header: Error<&str> = Error::InvalidHeader(vec![0xff], 4);
+    assert_eq!(header.to_string(), "invalid header [255] at byte 4");
+
+    let timeout: Error<&str> = Error::Timeout;
+    assert_eq!(timeout.to_string(), "Timeout");
+
+    let version = Version {
+        major: 1,
+        minor: 2,
+        patch: 3,
+    };
+    assert_eq!(version.to_string(), "1.2.3");
+    assert_eq!(Unknown.to_string(), "Unknown");
+}
@@ -0,0 +1,13 @@
+// There's no field by field output to fall back on for Display, so variants
+// with fields need a format string.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+pub enum Shape {
+    #[display("circle of radius {radius}")]
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+fn main() {}
@@ -0,0 +1,68 @@
+// Bounds are inferred from wherever a type parameter appears in the formatted
+// fields, however deeply nested: behind references, in arrays, tuples and
+// through associated types. Nothing is required of parameters that only show
+// up where they don't get formatted, like in PhantomData anywhere in a type, in
+// function pointer signatures or in skipped and redacted fields. Fields
+// printed through a format spec such as {:x} get that trait as their bound
+// instead of Debug.
+
+use derive_debug::CustomDebug;
+use std::fmt::{Debug, LowerHex};
+use std::marker::PhantomData;
+
+pub trait Trait {
+    type Item;
+}
+
+#[derive(CustomDebug)]
+pub struct Shapes<'a, A, B, C, D: Trait, E, F, G, H> {
+    reference: &'a A,
+    array: [B; 2],
+    tuple: (C, u8),
+    projection: Option<Vec<D::Item>>,
+    qualified: Vec<<D as Trait>::Item>,
+    nested_marker: Option<PhantomData<E>>,
+    callback: fn(F) -> E,
+    #[debug(skip)]
+    skipped: G,
+    #[debug("{:#x}")]
+    hex: H,
+}
+
+fn assert_debug<T: Debug>() {}
+
+fn assert_hex_only<H: LowerHex>() {
+    assert_debug::<Shapes<'static, u8, u8, u8, Id, NotDebug, NotDebug, NotDebug, H>>();
+}
+
+pub struct NotDebug;
+
+pub struct Id;
+
+impl Trait for Id {
+    type Item = u8;
+}
+
+fn main() {
+    assert_hex_only::<u32>();
+
+    fn callback(_: NotDebug) -> NotDebug {
+        NotDebug
+    }
+    let shapes: Shapes<u8, u8, u8, Id, NotDebug, NotDebug, NotDebug, u32> = Shapes {
+        reference: &1,
+        array: [2, 3],
+        tuple: (4, 5),
+        projection: Some(vec![6]),
+        qualified: vec![7],
+        nested_marker: None,
+        callback,
+        skipped: NotDebug,
+        hex: 255,
+    };
+    let debug = format!("{:?}", shapes);
+    assert!(debug.starts_with(
+        "Shapes { reference: 1, array: [2, 3], tuple: (4, 5), projection: Some([6]), qualified: [7], nested_marker: None, callback: "
+    ));
+    assert!(debug.ends_with(", hex: 0xff, .. }"));
+}
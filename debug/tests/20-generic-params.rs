@@ -0,0 +1,75 @@
+// Lifetime and const parameters, parameter defaults and where clauses are all
+// carried over to the impl. A where clause on the type is kept and the
+// inferred or #[debug(bound = "...")] predicates are added to it, the latter
+// taking any number of comma separated predicates.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Ring<T, const N: usize = 4> {
+    slots: [Option<T>; N],
+    head: usize,
+}
+
+#[derive(CustomDebug)]
+pub struct View<'a, T: ?Sized = str> {
+    inner: &'a T,
+}
+
+#[derive(CustomDebug)]
+pub struct Sorted<T>(Vec<T>)
+where
+    T: Ord;
+
+#[derive(CustomDebug)]
+pub enum Either<L, R>
+where
+    L: Clone,
+{
+    Left(L),
+    Right(R),
+}
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+#[debug(bound = "T::Value: Debug, U: Debug")]
+pub struct Bounded<T: Trait, U>
+where
+    U: Copy,
+{
+    values: Vec<T::Value>,
+    extra: U,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Ring<u8>>();
+    assert_debug::<Ring<u8, 16>>();
+    assert_debug::<View<'static>>();
+    assert_debug::<View<'static, [u8]>>();
+    assert_debug::<Sorted<u8>>();
+    assert_debug::<Either<u8, &str>>();
+    assert_debug::<Bounded<Id, u8>>();
+
+    let ring: Ring<u8, 2> = Ring {
+        slots: [Some(1), None],
+        head: 0,
+    };
+    assert_eq!(
+        format!("{:?}", ring),
+        "Ring { slots: [Some(1), None], head: 0 }"
+    );
+    assert_eq!(format!("{:?}", View { inner: "hi" }), r#"View { inner: "hi" }"#);
+    assert_eq!(format!("{:?}", Sorted(vec![1, 2])), "Sorted([1, 2])");
+}
@@ -0,0 +1,57 @@
+// Large collections and long strings make for unreadable output. A field with
+// #[debug(limit = N)] only prints its first N elements followed by how many
+// were left out, and one with #[debug(max_len = N)] only prints the first N
+// characters of a string. `limit` works on anything whose reference iterates,
+// `max_len` on anything that's `AsRef<str>`, which is the bound a generic
+// field gets.
+
+use derive_debug::CustomDebug;
+use std::collections::BTreeMap;
+
+#[derive(CustomDebug)]
+pub struct Upload {
+    #[debug(limit = 4)]
+    chunk: Vec<u8>,
+    #[debug(limit = 1)]
+    headers: BTreeMap<&'static str, &'static str>,
+    #[debug(limit = 8)]
+    tags: [&'static str; 2],
+    #[debug(max_len = 5)]
+    description: String,
+    #[debug(max_len = 16)]
+    name: &'static str,
+}
+
+#[derive(CustomDebug)]
+pub struct Page<'a> {
+    #[debug(limit = 2)]
+    items: &'a [u32],
+}
+
+#[derive(CustomDebug)]
+pub struct Note<T> {
+    #[debug(max_len = 3)]
+    text: T,
+}
+
+fn main() {
+    let upload = Upload {
+        chunk: vec![0; 4096],
+        headers: BTreeMap::from([("accept", "*/*"), ("host", "example.com")]),
+        tags: ["a", "b"],
+        description: "a very long description".to_owned(),
+        name: "report.pdf",
+    };
+    assert_eq!(
+        format!("{:?}", upload),
+        r#"Upload { chunk: [0, 0, 0, 0, … (4092 more)], headers: [("accept", "*/*"), … (1 more)], tags: ["a", "b"], description: "a ver"… (18 more), name: "report.pdf" }"#
+    );
+
+    let page = Page { items: &[1, 2, 3] };
+    assert_eq!(format!("{:?}", page), "Page { items: [1, 2, … (1 more)] }");
+
+    let note = Note {
+        text: "shopping".to_owned(),
+    };
+    assert_eq!(format!("{:?}", note), r#"Note { text: "sho"… (5 more) }"#);
+}
@@ -0,0 +1,74 @@
+// #[debug(hex)], #[debug(bin)] and #[debug(octal)] print integers in that
+// base, and the integers of a Vec, array or slice element by element.
+// #[debug(bytes)] prints anything that's `AsRef<[u8]>` as one hex string, and
+// a generic field with it is bound by `AsRef<[u8]>`.
+// Like the std formatting traits they follow the alternate flag, so {:#?}
+// adds the `0x`, `0b` or `0o` prefix.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Registers<'a, T> {
+    #[debug(hex)]
+    pc: u32,
+    #[debug(bin)]
+    flags: u8,
+    #[debug(octal)]
+    mode: u16,
+    #[debug(hex)]
+    stack: Vec<u16>,
+    #[debug(hex)]
+    window: &'a [u8],
+    #[debug(hex)]
+    pair: [T; 2],
+    #[debug(bytes)]
+    hash: [u8; 4],
+}
+
+#[derive(CustomDebug)]
+pub struct Digest<B> {
+    #[debug(bytes)]
+    bytes: B,
+}
+
+fn main() {
+    let registers = Registers {
+        pc: 0xc000,
+        flags: 0b101,
+        mode: 0o755,
+        stack: vec![0x10, 0xff],
+        window: &[0xa, 0xb],
+        pair: [1u64, 0xdead],
+        hash: [0xde, 0xad, 0xbe, 0xef],
+    };
+    assert_eq!(
+        format!("{:?}", registers),
+        "Registers { pc: c000, flags: 101, mode: 755, stack: [10, ff], window: [a, b], pair: [1, dead], hash: deadbeef }"
+    );
+    assert_eq!(
+        format!("{:#?}", registers),
+        "Registers {
+    pc: 0xc000,
+    flags: 0b101,
+    mode: 0o755,
+    stack: [
+        0x10,
+        0xff,
+    ],
+    window: [
+        0xa,
+        0xb,
+    ],
+    pair: [
+        0x1,
+        0xdead,
+    ],
+    hash: 0xdeadbeef,
+}"
+    );
+
+    let digest = Digest {
+        bytes: vec![0xca, 0xfe],
+    };
+    assert_eq!(format!("{:?}", digest), "Digest { bytes: cafe }");
+}
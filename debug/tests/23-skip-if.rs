@@ -0,0 +1,82 @@
+// Sparse values read better without their empty fields. A field with
+// #[debug(skip_if = path::to::fn)] is left out whenever the function, given a
+// reference to the field, returns true. #[debug(skip_defaults)] on a struct,
+// enum or variant leaves out every field equal to its type's default value.
+//
+// Unlike #[debug(skip)] these don't end the output with `..`, the missing
+// fields are empty rather than hidden.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+pub struct Config {
+    name: &'static str,
+    #[debug(skip_if = "Option::is_none")]
+    proxy: Option<&'static str>,
+    #[debug(skip_if = Vec::is_empty)]
+    mirrors: Vec<&'static str>,
+}
+
+#[derive(CustomDebug)]
+#[debug(skip_defaults)]
+pub struct Limits<T> {
+    retries: u32,
+    timeout: Option<u64>,
+    extra: Vec<T>,
+    // Always shown, whatever skip_defaults says
+    #[debug(skip_if = "never")]
+    verbose: bool,
+}
+
+fn never(_: &bool) -> bool {
+    false
+}
+
+#[derive(CustomDebug)]
+pub enum Event {
+    #[debug(skip_defaults)]
+    Retry { attempt: u32, reason: String },
+    Other(u32),
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_debug::<Limits<u8>>();
+
+    let config = Config {
+        name: "main",
+        proxy: None,
+        mirrors: vec![],
+    };
+    assert_eq!(format!("{:?}", config), r#"Config { name: "main" }"#);
+
+    let config = Config {
+        name: "main",
+        proxy: Some("localhost:3128"),
+        mirrors: vec!["a"],
+    };
+    assert_eq!(
+        format!("{:?}", config),
+        r#"Config { name: "main", proxy: Some("localhost:3128"), mirrors: ["a"] }"#
+    );
+
+    let limits: Limits<u8> = Limits {
+        retries: 3,
+        timeout: None,
+        extra: vec![],
+        verbose: false,
+    };
+    assert_eq!(
+        format!("{:?}", limits),
+        "Limits { retries: 3, verbose: false }"
+    );
+
+    let retry = Event::Retry {
+        attempt: 2,
+        reason: String::new(),
+    };
+    assert_eq!(format!("{:?}", retry), "Retry { attempt: 2 }");
+    assert_eq!(format!("{:?}", Event::Other(0)), "Other(0)");
+}
@@ -0,0 +1,133 @@
+// Some types are only there to group or wrap other values, and their own name
+// in the output is noise.
+//
+// A named field with #[debug(flatten)] prints the fields of its own
+// CustomDebug struct in its place, as if they were declared in the parent. The
+// inner struct keeps its own attributes, ie. skipped or renamed fields stay
+// that way when flattened. A generic field can be flattened too, the derived
+// impl then requires its type to be flattenable.
+//
+// A struct with #[debug(transparent)] and a single field is printed exactly as
+// that field, without a name or braces around it. Other fields are allowed as
+// long as they're skipped.
+
+use derive_debug::CustomDebug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub struct Timestamps {
+    created: u64,
+    #[debug(rename = "modified")]
+    updated: u64,
+    #[debug(skip)]
+    cached: bool,
+}
+
+#[derive(CustomDebug)]
+pub struct Document<T> {
+    title: &'static str,
+    #[debug(flatten)]
+    times: Timestamps,
+    #[debug(flatten, skip_if = "Extra::is_empty")]
+    extra: Extra<T>,
+}
+
+#[derive(CustomDebug)]
+pub struct Extra<T> {
+    tags: Vec<T>,
+}
+
+impl<T> Extra<T> {
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Tracked<T> {
+    id: u32,
+    #[debug(flatten)]
+    inner: T,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct UserId(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Tagged<T> {
+    #[debug("{:#x}")]
+    value: u32,
+    #[debug(skip)]
+    marker: PhantomData<T>,
+}
+
+#[derive(CustomDebug)]
+pub enum Change {
+    Edited {
+        by: UserId,
+        #[debug(flatten)]
+        at: Timestamps,
+    },
+}
+
+fn main() {
+    let document = Document::<&str> {
+        title: "notes",
+        times: Timestamps {
+            created: 1,
+            updated: 2,
+            cached: true,
+        },
+        extra: Extra { tags: vec![] },
+    };
+    assert_eq!(
+        format!("{:?}", document),
+        r#"Document { title: "notes", created: 1, modified: 2 }"#
+    );
+
+    let document = Document {
+        extra: Extra { tags: vec!["draft"] },
+        ..document
+    };
+    assert_eq!(
+        format!("{:?}", document),
+        r#"Document { title: "notes", created: 1, modified: 2, tags: ["draft"] }"#
+    );
+
+    let tracked = Tracked {
+        id: 1,
+        inner: Timestamps {
+            created: 3,
+            updated: 4,
+            cached: false,
+        },
+    };
+    assert_eq!(
+        format!("{:?}", tracked),
+        "Tracked { id: 1, created: 3, modified: 4 }"
+    );
+
+    assert_eq!(format!("{:?}", UserId(7)), "7");
+    assert_eq!(format!("{:#?}", UserId(7)), "7");
+
+    let tagged = Tagged::<String> {
+        value: 255,
+        marker: PhantomData,
+    };
+    assert_eq!(format!("{:?}", tagged), "0xff");
+
+    let change = Change::Edited {
+        by: UserId(7),
+        at: Timestamps {
+            created: 1,
+            updated: 2,
+            cached: false,
+        },
+    };
+    assert_eq!(
+        format!("{:?}", change),
+        "Edited { by: 7, created: 1, modified: 2 }"
+    );
+}
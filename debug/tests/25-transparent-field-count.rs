@@ -0,0 +1,13 @@
+// A transparent struct prints a single field, which one is ambiguous when more
+// than one of them isn't skipped.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Range {
+    start: u32,
+    end: u32,
+}
+
+fn main() {}
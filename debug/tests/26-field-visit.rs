@@ -0,0 +1,123 @@
+// Structured loggers want the fields of a value as key/value pairs rather than
+// one formatted string. #[debug(visit)] on a struct or enum also implements
+// derive_debug::FieldVisit, handing each field to a FieldVisitor along with
+// the value Debug would print for it.
+//
+// The field attributes apply the same way: skipped fields aren't visited,
+// redacted ones are visited as their placeholder, and renames and format
+// strings are used as they are in the Debug output.
+
+use derive_debug::{CustomDebug, FieldVisit, FieldVisitor};
+use std::fmt::Debug;
+
+#[derive(CustomDebug)]
+#[debug(visit)]
+pub struct Request {
+    method: &'static str,
+    #[debug(rename = "url")]
+    path: String,
+    #[debug("{}ms")]
+    elapsed: u64,
+    #[debug(redact)]
+    token: String,
+    #[debug(skip)]
+    body: Vec<u8>,
+    #[debug(flatten)]
+    client: Client,
+}
+
+#[derive(CustomDebug)]
+#[debug(visit)]
+pub struct Client {
+    #[debug(skip_if = "Option::is_none")]
+    agent: Option<&'static str>,
+}
+
+#[derive(CustomDebug)]
+#[debug(visit)]
+pub enum Outcome<T> {
+    Ok(T),
+    #[debug(rename_all = "camelCase")]
+    Failed { error_code: u16 },
+}
+
+// Visiting ignores the format string `fmt` uses, every field is visited through
+// `Debug` including the ones the string leaves out or prints with `Display`
+#[derive(CustomDebug)]
+#[debug("Span({start}..{end})")]
+#[debug(visit)]
+pub struct Span<T> {
+    start: T,
+    end: T,
+}
+
+#[derive(CustomDebug)]
+#[debug(visit)]
+pub struct Tagged<T> {
+    #[debug(flatten)]
+    span: Span<T>,
+    tag: u8,
+}
+
+// Collects the fields as `name=value` pairs
+#[derive(Default)]
+struct Pairs(Vec<String>);
+
+impl FieldVisitor for Pairs {
+    fn visit_field(&mut self, name: &str, value: &dyn Debug) {
+        self.0.push(format!("{}={:?}", name, value));
+    }
+}
+
+fn pairs(value: &dyn FieldVisit) -> Vec<String> {
+    let mut pairs = Pairs::default();
+    value.visit_fields(&mut pairs);
+    pairs.0
+}
+
+fn main() {
+    let request = Request {
+        method: "GET",
+        path: "/health".to_owned(),
+        elapsed: 12,
+        token: "hunter2".to_owned(),
+        body: vec![],
+        client: Client { agent: None },
+    };
+    assert_eq!(
+        pairs(&request),
+        [
+            r#"method="GET""#,
+            r#"url="/health""#,
+            "elapsed=12ms",
+            "token=<redacted>",
+        ]
+    );
+
+    let request = Request {
+        client: Client {
+            agent: Some("curl"),
+        },
+        ..request
+    };
+    assert_eq!(pairs(&request).last().unwrap(), r#"agent=Some("curl")"#);
+
+    assert_eq!(pairs(&Outcome::Ok(5)), ["0=5"]);
+    assert_eq!(
+        pairs(&Outcome::<()>::Failed { error_code: 503 }),
+        ["errorCode=503"]
+    );
+
+    let span = Span { start: 1, end: 4 };
+    assert_eq!(format!("{:?}", span), "Span(1..4)");
+    assert_eq!(pairs(&span), ["start=1", "end=4"]);
+    assert_eq!(
+        pairs(&Tagged { span, tag: 7 }),
+        ["start=1", "end=4", "tag=7"]
+    );
+
+    // Closures are visitors too
+    let mut names = vec![];
+    request.visit_fields(&mut |name: &str, _: &dyn Debug| names.push(name.to_owned()));
+    assert_eq!(names, ["method", "url", "elapsed", "token", "agent"]);
+}
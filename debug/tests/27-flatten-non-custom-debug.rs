@@ -0,0 +1,18 @@
+// Only structs deriving CustomDebug can be flattened, their fields are what
+// ends up in the parent's output. Anything else is an error on the field.
+
+use derive_debug::CustomDebug;
+
+#[derive(Debug)]
+pub struct Timestamps {
+    created: u64,
+}
+
+#[derive(CustomDebug)]
+pub struct Document {
+    title: &'static str,
+    #[debug(flatten)]
+    times: Timestamps,
+}
+
+fn main() {}
@@ -9,4 +9,23 @@ fn tests() {
     t.pass("tests/06-bound-trouble.rs");
     t.pass("tests/07-associated-type.rs");
     t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-enum.rs");
+    t.pass("tests/10-tuple-and-unit-struct.rs");
+    t.pass("tests/11-skip-and-rename.rs");
+    t.compile_fail("tests/12-misplaced-attribute.rs");
+    t.pass("tests/13-redact.rs");
+    t.pass("tests/14-debug-with.rs");
+    t.pass("tests/15-struct-format.rs");
+    t.compile_fail("tests/16-unknown-format-field.rs");
+    t.pass("tests/17-custom-display.rs");
+    t.compile_fail("tests/18-display-missing-format.rs");
+    t.pass("tests/19-bound-inference.rs");
+    t.pass("tests/20-generic-params.rs");
+    t.pass("tests/21-limit-and-max-len.rs");
+    t.pass("tests/22-numeric-presets.rs");
+    t.pass("tests/23-skip-if.rs");
+    t.pass("tests/24-flatten-and-transparent.rs");
+    t.compile_fail("tests/25-transparent-field-count.rs");
+    t.pass("tests/26-field-visit.rs");
+    t.compile_fail("tests/27-flatten-non-custom-debug.rs");
 }